@@ -0,0 +1,323 @@
+#[allow(dead_code)]
+#[path = "src/manifest/schema.rs"]
+mod schema;
+
+use std::{collections::BTreeMap, env, fs, ops::Range, path::Path};
+use toml_edit::{ImDocument, Item, Table};
+
+const MANIFEST: &str = "portfolio.toml";
+const LOCALES_DIR: &str = "locales";
+const REPO_STATS: &str = "repo-stats.json";
+/// Written by `examples/build_images.rs`, next to the variants it describes.
+const IMAGES_JSON: &str = "assets/build/images.json";
+
+fn main() {
+    println!("cargo:rerun-if-changed={}", MANIFEST);
+    println!("cargo:rerun-if-changed={}", LOCALES_DIR);
+    println!("cargo:rerun-if-changed=src/manifest/schema.rs");
+    println!("cargo:rustc-check-cfg=cfg(invalid_content)");
+
+    let out_dir = env::var("OUT_DIR").unwrap();
+    let portfolio = build_manifest(Path::new(&out_dir));
+    let images_ok = portfolio
+        .as_ref()
+        .is_some_and(|p| build_images(Path::new(&out_dir), p));
+    let stats_ok = portfolio
+        .as_ref()
+        .is_some_and(|p| build_repo_stats(Path::new(&out_dir), p));
+    let locales_ok = build_locales(Path::new(&out_dir));
+    if portfolio.is_none() || !images_ok || !stats_ok || !locales_ok {
+        // Failing here would also stop the examples, which only share the schema and may be
+        // what fixes the manifest (import_resume). The app refuses to compile instead.
+        for file in [
+            "portfolio.json",
+            "images.json",
+            "repo_stats.json",
+            "locales.json",
+        ] {
+            let path = Path::new(&out_dir).join(file);
+            if !path.exists() {
+                fs::write(path, "{}").unwrap();
+            }
+        }
+        println!("cargo:rustc-cfg=invalid_content");
+    }
+}
+
+fn build_manifest(out_dir: &Path) -> Option<schema::Portfolio> {
+    let source = read(MANIFEST)?;
+
+    let portfolio: schema::Portfolio = match toml::from_str(&source) {
+        Ok(p) => p,
+        Err(e) => {
+            report(MANIFEST, &source, e.span(), e.message());
+            return None;
+        }
+    };
+
+    let issues = portfolio.validate();
+    if !issues.is_empty() {
+        let doc = ImDocument::parse(source.as_str()).expect("manifest was already parsed once");
+        for issue in &issues {
+            report(
+                MANIFEST,
+                &source,
+                locate(doc.as_item(), &issue.path),
+                &issue.to_string(),
+            );
+        }
+        return None;
+    }
+    let warnings = portfolio.warnings();
+    if !warnings.is_empty() {
+        let doc = ImDocument::parse(source.as_str()).expect("manifest was already parsed once");
+        for warning in &warnings {
+            let at = match locate(doc.as_item(), &warning.path) {
+                Some(span) => {
+                    let (line, col) = line_col(&source, &span);
+                    format!("{}:{}:{}", MANIFEST, line, col)
+                }
+                None => MANIFEST.to_string(),
+            };
+            println!("cargo:warning={}: {}", at, warning);
+        }
+    }
+
+    fs::write(
+        out_dir.join("portfolio.json"),
+        serde_json::to_string(&portfolio).unwrap(),
+    )
+    .unwrap();
+    Some(portfolio)
+}
+
+/// Checks that every local image of the manifest exists and was processed by
+/// `examples/build_images.rs` since it last changed, and embeds the variants as `images.json`.
+/// Images are served from `/images`, or from `PORTFOLIO_IMAGES_URL` if that variable is set.
+/// An image that was not processed yet only produces a warning and is shown as is.
+fn build_images(out_dir: &Path, portfolio: &schema::Portfolio) -> bool {
+    println!("cargo:rerun-if-changed={}", schema::IMAGES_DIR);
+    println!("cargo:rerun-if-changed={}", IMAGES_JSON);
+    println!("cargo:rerun-if-env-changed=PORTFOLIO_IMAGES_URL");
+    let base = env::var("PORTFOLIO_IMAGES_URL").unwrap_or_else(|_| "/images".to_string());
+    let processed: BTreeMap<String, schema::ProcessedImage> = match fs::read_to_string(IMAGES_JSON)
+    {
+        Ok(json) => match serde_json::from_str(&json) {
+            Ok(processed) => processed,
+            Err(e) => {
+                error(&format!("{}: {}", IMAGES_JSON, e));
+                return false;
+            }
+        },
+        Err(_) => BTreeMap::new(),
+    };
+    let source = read(MANIFEST).unwrap_or_default();
+    let doc = ImDocument::parse(source.as_str()).ok();
+    let span = |path: &str| doc.as_ref().and_then(|doc| locate(doc.as_item(), path));
+    let mut ok = true;
+    let mut sets = BTreeMap::new();
+    for (path, image) in portfolio.images() {
+        if !image.starts_with(schema::IMAGES_DIR) || sets.contains_key(image) {
+            continue;
+        }
+        let hash = match fs::read(image) {
+            Ok(bytes) => schema::content_hash(&bytes),
+            Err(e) => {
+                let message = format!("{}: {}: {}", path, image, e);
+                report(MANIFEST, &source, span(&path), &message);
+                ok = false;
+                continue;
+            }
+        };
+        match processed.get(image) {
+            Some(p) if p.hash == hash => {
+                sets.insert(image.to_string(), p.image_set(base.trim_end_matches('/')));
+            }
+            _ => {
+                let at = match span(&path) {
+                    Some(span) => {
+                        let (line, col) = line_col(&source, &span);
+                        format!("{}:{}:{}", MANIFEST, line, col)
+                    }
+                    None => MANIFEST.to_string(),
+                };
+                println!(
+                    "cargo:warning={}: {}: {} has no up-to-date variants, \
+                     run `cargo run --release --example build_images`",
+                    at, path, image
+                );
+            }
+        }
+    }
+    fs::write(
+        out_dir.join("images.json"),
+        serde_json::to_string(&sets).unwrap(),
+    )
+    .unwrap();
+    ok
+}
+
+/// Checks the repository statistics snapshot against the manifest and embeds it as
+/// `repo_stats.json`. Without a snapshot the projects simply show no statistics.
+fn build_repo_stats(out_dir: &Path, portfolio: &schema::Portfolio) -> bool {
+    println!("cargo:rerun-if-changed={}", REPO_STATS);
+    let snapshot: schema::RepoSnapshot = match fs::read_to_string(REPO_STATS) {
+        Ok(json) => match serde_json::from_str(&json) {
+            Ok(snapshot) => snapshot,
+            Err(e) => {
+                error(&format!("{}: {}", REPO_STATS, e));
+                return false;
+            }
+        },
+        Err(_) => schema::RepoSnapshot::default(),
+    };
+    let (errors, warnings) = snapshot.validate(portfolio);
+    for issue in &errors {
+        error(&format!("{}: {}", REPO_STATS, issue));
+    }
+    for warning in &warnings {
+        println!("cargo:warning={}: {}", REPO_STATS, warning);
+    }
+    fs::write(
+        out_dir.join("repo_stats.json"),
+        serde_json::to_string(&snapshot).unwrap(),
+    )
+    .unwrap();
+    errors.is_empty()
+}
+
+/// Flattens every `locales/<code>.toml` bundle into `locales.json`. Bundles other than the
+/// default may only use keys of the default bundle; keys they miss fall back at runtime.
+fn build_locales(out_dir: &Path) -> bool {
+    let mut ok = true;
+    let mut bundles = BTreeMap::new();
+    for (i, code) in schema::LOCALES.iter().enumerate() {
+        let path = format!("{}/{}.toml", LOCALES_DIR, code);
+        let Some(source) = read(&path) else {
+            ok = false;
+            continue;
+        };
+        let doc = match ImDocument::parse(source.as_str()) {
+            Ok(doc) => doc,
+            Err(e) => {
+                report(&path, &source, e.span(), e.message());
+                ok = false;
+                continue;
+            }
+        };
+        let mut strings = BTreeMap::new();
+        let mut invalid = Vec::new();
+        flatten(doc.as_table(), "", &mut strings, &mut invalid);
+        for key in invalid {
+            let span = locate(doc.as_item(), &key);
+            report(&path, &source, span, &format!("{}: must be a string", key));
+            ok = false;
+        }
+        if i > 0 {
+            let default: &BTreeMap<String, String> = &bundles[schema::LOCALES[0]];
+            for key in strings.keys().filter(|k| !default.contains_key(*k)) {
+                let span = locate(doc.as_item(), key);
+                let message = format!("{}: not defined in the {} bundle", key, schema::LOCALES[0]);
+                report(&path, &source, span, &message);
+                ok = false;
+            }
+            let missing: Vec<_> = default
+                .keys()
+                .filter(|k| !strings.contains_key(*k))
+                .collect();
+            if !missing.is_empty() {
+                println!(
+                    "cargo:warning={}: no translation for {:?}, falling back to {}",
+                    path,
+                    missing,
+                    schema::LOCALES[0]
+                );
+            }
+        }
+        bundles.insert(code.to_string(), strings);
+    }
+    fs::write(
+        out_dir.join("locales.json"),
+        serde_json::to_string(&bundles).unwrap(),
+    )
+    .unwrap();
+    ok
+}
+
+fn flatten(
+    table: &Table,
+    prefix: &str,
+    strings: &mut BTreeMap<String, String>,
+    invalid: &mut Vec<String>,
+) {
+    for (key, item) in table.iter() {
+        let key = format!("{}{}", prefix, key);
+        match item {
+            Item::Table(t) => flatten(t, &format!("{}.", key), strings, invalid),
+            _ => match item.as_str() {
+                Some(s) => {
+                    strings.insert(key, s.to_string());
+                }
+                None => invalid.push(key),
+            },
+        }
+    }
+}
+
+fn read(path: &str) -> Option<String> {
+    fs::read_to_string(path)
+        .map_err(|e| error(&format!("{}: {}", path, e)))
+        .ok()
+}
+
+/// Errors are printed as build warnings, since the build script itself does not fail.
+fn error(message: &str) {
+    println!("cargo:warning=error: {}", message);
+}
+
+fn report(file: &str, source: &str, span: Option<Range<usize>>, message: &str) {
+    let Some(span) = span else {
+        error(&format!("{}: {}", file, message));
+        return;
+    };
+    let (line, col) = line_col(source, &span);
+    error(&format!("{}:{}:{}: {}", file, line, col, message));
+    println!(
+        "cargo:warning=  | {}",
+        source.lines().nth(line - 1).unwrap_or_default()
+    );
+}
+
+fn line_col(source: &str, span: &Range<usize>) -> (usize, usize) {
+    let before = &source[..span.start];
+    let line = before.matches('\n').count() + 1;
+    (
+        line,
+        before.len() - before.rfind('\n').map_or(0, |i| i + 1) + 1,
+    )
+}
+
+/// Resolves a `jobs[2].start` style path to the closest span in the document.
+fn locate(root: &Item, path: &str) -> Option<Range<usize>> {
+    let mut item = root;
+    let mut span = None;
+    for segment in path.split('.') {
+        let (key, indexes) = segment.split_once('[').unwrap_or((segment, ""));
+        let Some(next) = item.get(key) else { break };
+        item = next;
+        span = item.span().or(span);
+        for index in indexes.split('[').filter(|i| !i.is_empty()) {
+            let Some(next) = index
+                .trim_end_matches(']')
+                .parse::<usize>()
+                .ok()
+                .and_then(|i| item.get(i))
+            else {
+                return span;
+            };
+            item = next;
+            span = item.span().or(span);
+        }
+    }
+    span
+}
@@ -0,0 +1,304 @@
+//! Turns the local images of the manifest into resized WebP variants (and AVIF ones with the
+//! `avif` feature), named after a hash of their content so they can be cached forever:
+//!
+//!     cargo run --release --example build_images
+//!
+//! Trunk runs it before every build (see `Trunk.toml`). The variants go to
+//! `assets/build/images`, which Trunk copies next to the bundle, and are described in
+//! `assets/build/images.json`, which build.rs embeds. Variants that are no longer used are
+//! removed.
+#[allow(dead_code)]
+#[path = "../src/manifest/schema.rs"]
+mod schema;
+
+use image::{imageops::FilterType, DynamicImage, GenericImageView, ImageFormat};
+use schema::*;
+use std::collections::{BTreeMap, HashSet};
+use std::io::Cursor;
+use std::path::Path;
+use std::{fs, process};
+
+const MANIFEST: &str = "portfolio.toml";
+const IMAGES_OUT: &str = "assets/build/images";
+const IMAGES_JSON: &str = "assets/build/images.json";
+/// Widths of the variants; smaller images also get one at their own width.
+const WIDTHS: &[u32] = &[320, 640, 1280];
+
+/// The widths of the variants of an image `width` pixels wide, which is never enlarged.
+fn widths(width: u32) -> Vec<u32> {
+    let mut widths: Vec<u32> = WIDTHS.iter().copied().filter(|w| *w < width).collect();
+    if widths.len() < WIDTHS.len() {
+        widths.push(width);
+    }
+    widths
+}
+
+/// Writes the variants of `file`, a path relative to `root`, to `out`, adding their names to
+/// `written`.
+fn process_image(
+    root: &Path,
+    file: &str,
+    out: &Path,
+    written: &mut HashSet<String>,
+) -> Result<ProcessedImage, String> {
+    let bytes = fs::read(root.join(file)).map_err(|e| e.to_string())?;
+    let image = image::load_from_memory(&bytes).map_err(|e| e.to_string())?;
+    let path = Path::new(file);
+    let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or("image");
+    let extension = path.extension().and_then(|s| s.to_str()).unwrap_or("img");
+    let hash = content_hash(&bytes);
+    let name = format!("{}-{:08x}", stem, hash);
+    let mut write_once = |file: String, contents: &dyn Fn() -> Result<Vec<u8>, String>| {
+        let target = out.join(&file);
+        // The name changes with the content, so an existing file is up to date.
+        if !target.exists() {
+            fs::write(&target, contents()?).map_err(|e| e.to_string())?;
+        }
+        written.insert(file.clone());
+        Ok::<_, String>(file)
+    };
+
+    let (width, height) = image.dimensions();
+    let resized = |w: u32| match w == width {
+        true => image.clone(),
+        false => image.resize(w, u32::MAX, FilterType::Lanczos3),
+    };
+    let mut webp = Vec::new();
+    #[allow(unused_mut)]
+    let mut avif = Vec::new();
+    for w in widths(width) {
+        let file = write_once(format!("{}-{}.webp", name, w), &|| {
+            encode(&resized(w), ImageFormat::WebP)
+        })?;
+        webp.push((w, file));
+        #[cfg(feature = "avif")]
+        {
+            let file = write_once(format!("{}-{}.avif", name, w), &|| {
+                encode(&resized(w), ImageFormat::Avif)
+            })?;
+            avif.push((w, file));
+        }
+    }
+    let fallback = write_once(format!("{}.{}", name, extension), &|| Ok(bytes.clone()))?;
+    let [r, g, b] = image.thumbnail_exact(1, 1).to_rgb8().get_pixel(0, 0).0;
+
+    Ok(ProcessedImage {
+        hash,
+        width,
+        height,
+        fallback,
+        webp,
+        avif,
+        color: format!("#{:02x}{:02x}{:02x}", r, g, b),
+    })
+}
+
+fn encode(image: &DynamicImage, format: ImageFormat) -> Result<Vec<u8>, String> {
+    let mut bytes = Cursor::new(Vec::new());
+    let result = match format {
+        #[cfg(feature = "avif")]
+        ImageFormat::Avif => image.to_rgba8().write_with_encoder(
+            image::codecs::avif::AvifEncoder::new_with_speed_quality(&mut bytes, 8, 70),
+        ),
+        // The WebP encoder only takes 8-bit RGB(A).
+        _ => DynamicImage::ImageRgba8(image.to_rgba8()).write_to(&mut bytes, format),
+    };
+    result.map_err(|e| e.to_string())?;
+    Ok(bytes.into_inner())
+}
+
+/// Processes the local images of the manifest, found under `root`, into `out` and removes the
+/// variants there that no image uses. Nothing is removed when an image fails.
+fn build_images(
+    portfolio: &Portfolio,
+    root: &Path,
+    out: &Path,
+) -> Result<BTreeMap<String, ProcessedImage>, Vec<String>> {
+    fs::create_dir_all(out).map_err(|e| vec![format!("{}: {}", out.display(), e)])?;
+    let mut errors = Vec::new();
+    let mut images = BTreeMap::new();
+    let mut written = HashSet::new();
+    for (path, image) in portfolio.images() {
+        if !image.starts_with(IMAGES_DIR) || images.contains_key(image) {
+            continue;
+        }
+        match process_image(root, image, out, &mut written) {
+            Ok(processed) => {
+                images.insert(image.to_string(), processed);
+            }
+            Err(e) => errors.push(format!("{}: {}: {}", path, image, e)),
+        }
+    }
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+    if let Ok(entries) = fs::read_dir(out) {
+        for entry in entries.flatten() {
+            let name = entry.file_name().to_string_lossy().to_string();
+            if !written.contains(&name) && !name.starts_with('.') {
+                let _ = fs::remove_file(entry.path());
+            }
+        }
+    }
+    Ok(images)
+}
+
+fn main() {
+    let portfolio: Portfolio = fs::read_to_string(MANIFEST)
+        .map_err(|e| e.to_string())
+        .and_then(|s| toml::from_str(&s).map_err(|e| e.to_string()))
+        .unwrap_or_else(|e| {
+            eprintln!("error: {}: {}", MANIFEST, e);
+            process::exit(1);
+        });
+    let images =
+        build_images(&portfolio, Path::new("."), Path::new(IMAGES_OUT)).unwrap_or_else(|errors| {
+            for e in errors {
+                eprintln!("error: {}", e);
+            }
+            process::exit(1);
+        });
+    let json = serde_json::to_string_pretty(&images).unwrap();
+    if let Err(e) = fs::write(IMAGES_JSON, json + "\n") {
+        eprintln!("error: {}: {}", IMAGES_JSON, e);
+        process::exit(1);
+    }
+    eprintln!("{} images in {}", images.len(), IMAGES_OUT);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{Rgb, RgbImage};
+    use std::path::PathBuf;
+
+    /// An empty directory with `assets/images` in it, unique to the test.
+    fn scratch(test: &str) -> PathBuf {
+        let root = std::env::temp_dir().join(format!("build_images-{}-{}", process::id(), test));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join(IMAGES_DIR)).unwrap();
+        root
+    }
+
+    fn save(root: &Path, file: &str, width: u32, height: u32, color: [u8; 3]) {
+        RgbImage::from_pixel(width, height, Rgb(color))
+            .save(root.join(file))
+            .unwrap();
+    }
+
+    /// The manifest of the site, with its first images moved to the given local files.
+    fn portfolio(images: &[&str]) -> Portfolio {
+        let mut portfolio: Portfolio = toml::from_str(include_str!("../portfolio.toml")).unwrap();
+        portfolio.contact.email_img = images[0].to_string();
+        for (project, image) in portfolio.projects.iter_mut().zip(&images[1..]) {
+            project.img = image.to_string();
+        }
+        portfolio
+    }
+
+    #[test]
+    fn widths_never_enlarge() {
+        assert_eq!(widths(2000), [320, 640, 1280]);
+        assert_eq!(widths(1280), [320, 640, 1280]);
+        assert_eq!(widths(900), [320, 640, 900]);
+        assert_eq!(widths(640), [320, 640]);
+        assert_eq!(widths(200), [200]);
+    }
+
+    #[test]
+    fn hash_follows_the_content() {
+        assert_eq!(content_hash(b""), 0x4fd0_bfc1);
+        assert_eq!(content_hash(b"detee"), content_hash(b"detee"));
+        assert_ne!(content_hash(b"detee"), content_hash(b"deteE"));
+    }
+
+    #[test]
+    fn processes_an_image() {
+        let root = scratch("process");
+        let out = root.join("out");
+        fs::create_dir_all(&out).unwrap();
+        let file = "assets/images/logo.png";
+        save(&root, file, 900, 450, [10, 20, 30]);
+        let hash = content_hash(&fs::read(root.join(file)).unwrap());
+
+        let mut written = HashSet::new();
+        let image = process_image(&root, file, &out, &mut written).unwrap();
+        let name = format!("logo-{:08x}", hash);
+        assert_eq!((image.hash, image.width, image.height), (hash, 900, 450));
+        assert_eq!(image.fallback, format!("{}.png", name));
+        assert_eq!(image.color, "#0a141e");
+        let webp: Vec<_> = image.webp.iter().map(|(w, _)| *w).collect();
+        assert_eq!(webp, [320, 640, 900]);
+        for (w, variant) in &image.webp {
+            assert_eq!(variant, &format!("{}-{}.webp", name, w));
+            let (width, height) = image::open(out.join(variant)).unwrap().dimensions();
+            assert_eq!((width, height), (*w, *w / 2));
+        }
+        assert_eq!(written.len(), image.webp.len() + image.avif.len() + 1);
+        assert!(written.iter().all(|f| out.join(f).exists()));
+
+        // Other content gets other names; the same content, the same ones.
+        save(&root, file, 900, 450, [30, 20, 10]);
+        let changed = process_image(&root, file, &out, &mut HashSet::new()).unwrap();
+        assert_ne!(changed.fallback, image.fallback);
+        save(&root, file, 900, 450, [10, 20, 30]);
+        let again = process_image(&root, file, &out, &mut HashSet::new()).unwrap();
+        assert_eq!(again.fallback, image.fallback);
+        assert_eq!(again.webp, image.webp);
+        let _ = fs::remove_dir_all(root);
+    }
+
+    #[test]
+    fn builds_the_local_images_and_removes_unused_variants() {
+        let root = scratch("build");
+        let out = root.join("out");
+        fs::create_dir_all(&out).unwrap();
+        fs::write(out.join(".gitkeep"), "").unwrap();
+        fs::write(out.join("old-00000000-320.webp"), "").unwrap();
+        save(&root, "assets/images/a.png", 400, 300, [200, 0, 0]);
+        save(&root, "assets/images/b.png", 100, 100, [0, 200, 0]);
+        let a = "assets/images/a.png";
+        let b = "assets/images/b.png";
+        let portfolio = portfolio(&[a, a, b]);
+
+        let images = build_images(&portfolio, &root, &out).unwrap();
+        assert_eq!(images.keys().collect::<Vec<_>>(), [a, b]);
+        assert_eq!(images[b].webp, [(100, images[b].webp[0].1.clone())]);
+        assert!(out.join(".gitkeep").exists());
+        assert!(!out.join("old-00000000-320.webp").exists());
+        let mut used: Vec<_> = images
+            .values()
+            .flat_map(|i| i.webp.iter().chain(&i.avif).map(|(_, f)| f.clone()))
+            .chain(images.values().map(|i| i.fallback.clone()))
+            .collect();
+        used.push(".gitkeep".to_string());
+        used.sort();
+        let mut files: Vec<_> = fs::read_dir(&out)
+            .unwrap()
+            .map(|e| e.unwrap().file_name().to_string_lossy().to_string())
+            .collect();
+        files.sort();
+        assert_eq!(files, used);
+
+        let set = images[b].image_set("/images");
+        assert_eq!(set.fallback, format!("/images/{}", images[b].fallback));
+        assert_eq!(set.webp, format!("/images/{} 100w", images[b].webp[0].1));
+        let _ = fs::remove_dir_all(root);
+    }
+
+    #[test]
+    fn keeps_the_variants_when_an_image_fails() {
+        let root = scratch("missing");
+        let out = root.join("out");
+        fs::create_dir_all(&out).unwrap();
+        fs::write(out.join("old-00000000-320.webp"), "").unwrap();
+        save(&root, "assets/images/a.png", 400, 300, [200, 0, 0]);
+        let portfolio = portfolio(&["assets/images/a.png", "assets/images/gone.png"]);
+
+        let errors = build_images(&portfolio, &root, &out).unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].starts_with("projects[0].img: assets/images/gone.png: "));
+        assert!(out.join("old-00000000-320.webp").exists());
+        let _ = fs::remove_dir_all(root);
+    }
+}
@@ -0,0 +1,157 @@
+//! A stand-in for the endpoint of the contact form, which prints the messages it receives:
+//!
+//!     cargo run --example contact_server -- [address] [--fail]
+//!     PORTFOLIO_CONTACT_ENDPOINT=http://127.0.0.1:8787/contact trunk serve
+//!
+//! It listens on 127.0.0.1:8787 by default, answers CORS preflights, and refuses more than
+//! a few messages a minute from one address. `--fail` answers every message with an error,
+//! to try the failure path of the form.
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{IpAddr, TcpListener, TcpStream};
+use std::time::{Duration, Instant};
+use std::{env, process};
+
+const PATH: &str = "/contact";
+const BODY_MAX: usize = 16 * 1024;
+const RATE_LIMIT: usize = 5;
+const RATE_WINDOW: Duration = Duration::from_secs(60);
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Message {
+    name: String,
+    email: String,
+    message: String,
+}
+
+struct Request {
+    method: String,
+    path: String,
+    body: Vec<u8>,
+}
+
+fn read_request(stream: &TcpStream) -> Result<Request, String> {
+    let mut reader = BufReader::new(stream);
+    let mut line = String::new();
+    reader.read_line(&mut line).map_err(|e| e.to_string())?;
+    let mut parts = line.split_whitespace();
+    let method = parts.next().ok_or("empty request")?.to_string();
+    let path = parts.next().ok_or("no path")?.to_string();
+
+    let mut length = 0;
+    loop {
+        line.clear();
+        reader.read_line(&mut line).map_err(|e| e.to_string())?;
+        let header = line.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            if name.eq_ignore_ascii_case("content-length") {
+                length = value.trim().parse().map_err(|_| "bad content-length")?;
+            }
+        }
+    }
+    if length > BODY_MAX {
+        return Err("body too large".to_string());
+    }
+    let mut body = vec![0; length];
+    reader.read_exact(&mut body).map_err(|e| e.to_string())?;
+    Ok(Request { method, path, body })
+}
+
+fn respond(mut stream: &TcpStream, status: &str, body: &str) {
+    let response = format!(
+        "HTTP/1.1 {}\r\n\
+         Access-Control-Allow-Origin: *\r\n\
+         Access-Control-Allow-Methods: POST, OPTIONS\r\n\
+         Access-Control-Allow-Headers: Content-Type\r\n\
+         Content-Type: text/plain; charset=utf-8\r\n\
+         Content-Length: {}\r\n\
+         Connection: close\r\n\r\n{}",
+        status,
+        body.len(),
+        body
+    );
+    let _ = stream.write_all(response.as_bytes());
+}
+
+struct Server {
+    fail: bool,
+    sent: HashMap<IpAddr, Vec<Instant>>,
+}
+
+impl Server {
+    /// The status line and body of the response.
+    fn handle(&mut self, peer: IpAddr, request: Request) -> (&'static str, String) {
+        match (request.method.as_str(), request.path == PATH) {
+            ("OPTIONS", true) => return ("204 No Content", String::new()),
+            ("POST", true) => {}
+            (_, true) => return ("405 Method Not Allowed", String::new()),
+            (_, false) => return ("404 Not Found", String::new()),
+        }
+        let sent = self.sent.entry(peer).or_default();
+        sent.retain(|t| t.elapsed() < RATE_WINDOW);
+        if sent.len() >= RATE_LIMIT {
+            return ("429 Too Many Requests", "slow down".to_string());
+        }
+        let message: Message = match serde_json::from_slice(&request.body) {
+            Ok(m) => m,
+            Err(e) => return ("400 Bad Request", e.to_string()),
+        };
+        if [&message.name, &message.email, &message.message]
+            .iter()
+            .any(|field| field.trim().is_empty())
+        {
+            return ("400 Bad Request", "empty field".to_string());
+        }
+        if self.fail {
+            return (
+                "500 Internal Server Error",
+                "failing on purpose".to_string(),
+            );
+        }
+        sent.push(Instant::now());
+        println!(
+            "--- from {} <{}>\n{}\n",
+            message.name, message.email, message.message
+        );
+        ("200 OK", "sent".to_string())
+    }
+}
+
+fn main() {
+    let mut address = "127.0.0.1:8787".to_string();
+    let mut fail = false;
+    for arg in env::args().skip(1) {
+        match arg.as_str() {
+            "--fail" => fail = true,
+            _ => address = arg,
+        }
+    }
+    let listener = TcpListener::bind(&address).unwrap_or_else(|e| {
+        eprintln!("error: {}: {}", address, e);
+        process::exit(1);
+    });
+    eprintln!("listening on http://{}{}", address, PATH);
+
+    let mut server = Server {
+        fail,
+        sent: HashMap::new(),
+    };
+    for stream in listener.incoming() {
+        let Ok(stream) = stream else { continue };
+        let Ok(peer) = stream.peer_addr() else {
+            continue;
+        };
+        let _ = stream.set_read_timeout(Some(Duration::from_secs(5)));
+        let (status, body) = match read_request(&stream) {
+            Ok(request) => server.handle(peer.ip(), request),
+            Err(e) => ("400 Bad Request", e),
+        };
+        eprintln!("{} {}", peer.ip(), status);
+        respond(&stream, status, &body);
+    }
+}
@@ -0,0 +1,48 @@
+//! Writes the validated manifest as the JSON document that `--features runtime-content` fetches:
+//!
+//!     cargo run --example export_content -- [output]
+//!
+//! The output defaults to `assets/build/portfolio.json`, which Trunk can copy next to the
+//! bundle. The document has the same schema as the copy build.rs embeds.
+#[allow(dead_code)]
+#[path = "../src/manifest/schema.rs"]
+mod schema;
+
+use schema::*;
+use std::path::Path;
+use std::{env, fs, process};
+
+const MANIFEST: &str = "portfolio.toml";
+const OUTPUT: &str = "assets/build/portfolio.json";
+
+fn main() {
+    let output = env::args().nth(1).unwrap_or_else(|| OUTPUT.to_string());
+    let portfolio: Portfolio = fs::read_to_string(MANIFEST)
+        .map_err(|e| e.to_string())
+        .and_then(|s| toml::from_str(&s).map_err(|e| e.to_string()))
+        .unwrap_or_else(|e| {
+            eprintln!("error: {}: {}", MANIFEST, e);
+            process::exit(1);
+        });
+    let issues = portfolio.validate();
+    if !issues.is_empty() {
+        for issue in issues {
+            eprintln!("error: {}: {}", MANIFEST, issue);
+        }
+        process::exit(1);
+    }
+    for warning in portfolio.warnings() {
+        eprintln!("warning: {}: {}", MANIFEST, warning);
+    }
+    if let Some(dir) = Path::new(&output)
+        .parent()
+        .filter(|d| !d.as_os_str().is_empty())
+    {
+        let _ = fs::create_dir_all(dir);
+    }
+    let json = serde_json::to_string_pretty(&portfolio).unwrap();
+    if let Err(e) = fs::write(&output, json + "\n") {
+        eprintln!("error: {}: {}", output, e);
+        process::exit(1);
+    }
+}
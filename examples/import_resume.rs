@@ -0,0 +1,44 @@
+//! Bootstraps `portfolio.toml` from an existing JSON Resume:
+//!
+//!     cargo run --example import_resume -- resume.json portfolio.toml
+//!
+//! The manifest is only written once the resume was read, so a failed import leaves the
+//! previous one in place.
+#[path = "../src/manifest/json_resume.rs"]
+mod json_resume;
+#[allow(dead_code)]
+#[path = "../src/manifest/schema.rs"]
+mod schema;
+
+use schema::*;
+use std::{env, fs, process};
+
+fn main() {
+    let (Some(path), Some(output)) = (env::args().nth(1), env::args().nth(2)) else {
+        eprintln!("usage: import_resume <resume.json> <portfolio.toml>");
+        process::exit(2);
+    };
+    let resume: json_resume::Resume = fs::read_to_string(&path)
+        .map_err(|e| e.to_string())
+        .and_then(|json| serde_json::from_str(&json).map_err(|e| e.to_string()))
+        .unwrap_or_else(|e| {
+            eprintln!("error: {}: {}", path, e);
+            process::exit(1);
+        });
+    // The manifest would not parse with these, so they are fixed in the resume first.
+    let dates = resume.date_issues();
+    if !dates.is_empty() {
+        for issue in dates {
+            eprintln!("error: {}: {}", path, issue);
+        }
+        process::exit(1);
+    }
+    let portfolio = Portfolio::from(resume);
+    for issue in portfolio.validate() {
+        eprintln!("todo: {}", issue);
+    }
+    if let Err(e) = fs::write(&output, toml::to_string_pretty(&portfolio).unwrap()) {
+        eprintln!("error: {}: {}", output, e);
+        process::exit(1);
+    }
+}
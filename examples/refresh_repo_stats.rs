@@ -0,0 +1,193 @@
+//! Refreshes `repo-stats.json` from the GitHub API, for the projects whose `source` is on GitHub:
+//!
+//!     cargo run --example refresh_repo_stats
+//!
+//! Uses `curl`, with `GITHUB_TOKEN` when it is set. Projects that cannot be fetched keep
+//! their previous entry, so the site build never depends on the network.
+#[allow(dead_code)]
+#[path = "../src/manifest/schema.rs"]
+mod schema;
+
+use schema::*;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use std::time::{SystemTime, UNIX_EPOCH};
+use std::{env, fs, process};
+
+const MANIFEST: &str = "portfolio.toml";
+const REPO_STATS: &str = "repo-stats.json";
+const LANGUAGES: usize = 3;
+
+#[derive(Deserialize)]
+struct Repo {
+    stargazers_count: u32,
+    license: Option<License>,
+}
+
+#[derive(Deserialize)]
+struct License {
+    spdx_id: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct Commit {
+    commit: CommitDetails,
+}
+
+#[derive(Deserialize)]
+struct CommitDetails {
+    committer: Signature,
+}
+
+#[derive(Deserialize)]
+struct Signature {
+    date: String,
+}
+
+/// A repository on GitHub, optionally narrowed to a directory of one branch
+/// (`https://github.com/<owner>/<repo>/tree/<branch>/<path>`).
+struct Source {
+    repo: String,
+    branch: Option<String>,
+    path: Option<String>,
+}
+
+impl Source {
+    fn parse(url: &str) -> Option<Source> {
+        let rest = url.strip_prefix("https://github.com/")?;
+        let mut parts = rest.trim_end_matches('/').splitn(5, '/');
+        let owner = parts.next().filter(|s| !s.is_empty())?;
+        let repo = parts.next().filter(|s| !s.is_empty())?;
+        let (branch, path) = match parts.next() {
+            Some("tree") => (
+                parts.next().map(str::to_string),
+                parts.next().map(str::to_string),
+            ),
+            _ => (None, None),
+        };
+        Some(Source {
+            repo: format!("{}/{}", owner, repo.trim_end_matches(".git")),
+            branch,
+            path,
+        })
+    }
+
+    fn fetch(&self) -> Result<RepoStats, String> {
+        let repo: Repo = get(&format!("repos/{}", self.repo))?;
+        let mut languages: Vec<(String, u64)> =
+            get::<serde_json::Map<_, _>>(&format!("repos/{}/languages", self.repo))?
+                .into_iter()
+                .map(|(name, bytes)| (name, bytes.as_u64().unwrap_or(0)))
+                .collect();
+        languages.sort_by_key(|(_, bytes)| std::cmp::Reverse(*bytes));
+
+        let mut commits = format!("repos/{}/commits?per_page=1", self.repo);
+        if let Some(branch) = &self.branch {
+            commits.push_str(&format!("&sha={}", branch));
+        }
+        if let Some(path) = &self.path {
+            commits.push_str(&format!("&path={}", path));
+        }
+        let last_commit = get::<Vec<Commit>>(&commits)?
+            .into_iter()
+            .next()
+            .map(|c| c.commit.committer.date.chars().take(10).collect())
+            .ok_or("no commits")?;
+
+        Ok(RepoStats {
+            stars: repo.stargazers_count,
+            last_commit,
+            languages: languages
+                .into_iter()
+                .take(LANGUAGES)
+                .map(|(name, _)| name)
+                .collect(),
+            license: repo
+                .license
+                .and_then(|l| l.spdx_id)
+                .filter(|id| id != "NOASSERTION"),
+        })
+    }
+}
+
+fn get<T: DeserializeOwned>(endpoint: &str) -> Result<T, String> {
+    let mut curl = process::Command::new("curl");
+    curl.args(["--silent", "--show-error", "--fail", "--location"])
+        .args(["--header", "Accept: application/vnd.github+json"]);
+    if let Ok(token) = env::var("GITHUB_TOKEN") {
+        curl.args(["--header", &format!("Authorization: Bearer {}", token)]);
+    }
+    let output = curl
+        .arg(format!("https://api.github.com/{}", endpoint))
+        .output()
+        .map_err(|e| format!("could not run curl: {}", e))?;
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+    }
+    serde_json::from_slice(&output.stdout).map_err(|e| e.to_string())
+}
+
+/// Today in UTC, as "YYYY-MM-DD".
+fn today() -> String {
+    let days = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() / 86_400)
+        .unwrap_or(0) as i64;
+    // Howard Hinnant's civil_from_days.
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + (month <= 2) as i64;
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+fn main() {
+    let portfolio: Portfolio = fs::read_to_string(MANIFEST)
+        .map_err(|e| e.to_string())
+        .and_then(|s| toml::from_str(&s).map_err(|e| e.to_string()))
+        .unwrap_or_else(|e| {
+            eprintln!("error: {}: {}", MANIFEST, e);
+            process::exit(1);
+        });
+    let previous: RepoSnapshot = fs::read_to_string(REPO_STATS)
+        .ok()
+        .and_then(|s| serde_json::from_str(&s).ok())
+        .unwrap_or_default();
+
+    let mut snapshot = RepoSnapshot {
+        fetched: Some(today()),
+        ..Default::default()
+    };
+    let mut failed = false;
+    for project in &portfolio.projects {
+        let Some(source) = Source::parse(&project.source) else {
+            continue;
+        };
+        match source.fetch() {
+            Ok(stats) => {
+                eprintln!("{}: {} stars", project.id, stats.stars);
+                snapshot.repos.insert(project.id.clone(), stats);
+            }
+            Err(e) => {
+                eprintln!("warning: {}: {}", project.id, e);
+                failed = true;
+                if let Some(stats) = previous.repos.get(&project.id) {
+                    snapshot.repos.insert(project.id.clone(), stats.clone());
+                }
+            }
+        }
+    }
+    let json = serde_json::to_string_pretty(&snapshot).unwrap();
+    if let Err(e) = fs::write(REPO_STATS, json + "\n") {
+        eprintln!("error: {}: {}", REPO_STATS, e);
+        process::exit(1);
+    }
+    if failed {
+        process::exit(1);
+    }
+}
@@ -0,0 +1,217 @@
+//! Copies the assets that the site loads from other hosts into the repository:
+//!
+//!     cargo run --example vendor_assets
+//!
+//! Downloads the Bulmaswatch theme and the Josefin Sans font to `assets/vendor`, with the font
+//! stylesheet pointing at the local font files, and makes `index.html` use them. Downloads the
+//! remote images of the manifest to `assets/images` and points the manifest at the copies.
+//! Uses `curl`. Running it again only fetches what is still remote; commit the result.
+#[allow(dead_code)]
+#[path = "../src/manifest/schema.rs"]
+mod schema;
+
+use schema::*;
+use std::collections::HashMap;
+use std::path::Path;
+use std::{fs, process};
+
+const MANIFEST: &str = "portfolio.toml";
+const INDEX: &str = "index.html";
+const VENDOR_DIR: &str = "assets/vendor";
+
+const THEME_URL: &str = "https://jenil.github.io/bulmaswatch/cyborg/bulmaswatch.min.css";
+const THEME_FILE: &str = "bulmaswatch-cyborg.min.css";
+const FONT_URL: &str = "https://fonts.googleapis.com/css2?family=Josefin+Sans&display=swap";
+const FONT_FILE: &str = "josefin-sans.css";
+/// Google Fonts picks the font format by user agent; this one gets WOFF2.
+const FONT_USER_AGENT: &str =
+    "Mozilla/5.0 (X11; Linux x86_64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0 Safari/537.36";
+
+/// The `<head>` lines of `index.html` that load remote assets, and their local replacements.
+const INDEX_LINKS: &[(&str, &str)] = &[
+    (
+        r#"<link rel="stylesheet" type="text/css" href="https://jenil.github.io/bulmaswatch/cyborg/bulmaswatch.min.css" />"#,
+        r#"<link data-trunk rel="css" href="assets/vendor/bulmaswatch-cyborg.min.css" />"#,
+    ),
+    (
+        r#"<link rel="preconnect" href="https://fonts.googleapis.com">"#,
+        r#"<link data-trunk rel="css" href="assets/vendor/josefin-sans.css" />"#,
+    ),
+    (
+        r#"<link rel="preconnect" href="https://fonts.gstatic.com" crossorigin>"#,
+        r#"<link data-trunk rel="copy-dir" href="assets/vendor/fonts" />"#,
+    ),
+    (
+        r#"<link href="https://fonts.googleapis.com/css2?family=Josefin+Sans&display=swap" rel="stylesheet">"#,
+        "",
+    ),
+];
+
+fn fetch(url: &str, user_agent: Option<&str>) -> Result<Vec<u8>, String> {
+    let mut curl = process::Command::new("curl");
+    curl.args(["--silent", "--show-error", "--fail", "--location"]);
+    if let Some(user_agent) = user_agent {
+        curl.args(["--user-agent", user_agent]);
+    }
+    let output = curl
+        .arg(url)
+        .output()
+        .map_err(|e| format!("could not run curl: {}", e))?;
+    match output.status.success() {
+        true => Ok(output.stdout),
+        false => Err(String::from_utf8_lossy(&output.stderr).trim().to_string()),
+    }
+}
+
+fn write(path: &Path, contents: &[u8]) -> Result<(), String> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| format!("{}: {}", dir.display(), e))?;
+    }
+    fs::write(path, contents).map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+/// The last segment of a URL, without its query string.
+fn file_name(url: &str) -> &str {
+    let path = url.split(['?', '#']).next().unwrap_or(url);
+    path.rsplit('/').next().unwrap_or(path)
+}
+
+/// Where a remote image is saved: `assets/images/` and its file name, unless that file
+/// belongs to another image already, in which case a hash of the URL is added to the name.
+fn local_image(url: &str, owners: &HashMap<String, String>) -> String {
+    let name = file_name(url);
+    let plain = format!("{}{}", IMAGES_DIR, name);
+    let free = match owners.get(&plain) {
+        Some(owner) => owner == url,
+        None => !Path::new(&plain).exists(),
+    };
+    if free {
+        return plain;
+    }
+    let (stem, extension) = name.rsplit_once('.').unwrap_or((name, "img"));
+    format!(
+        "{}{}-{:08x}.{}",
+        IMAGES_DIR,
+        stem,
+        content_hash(url.as_bytes()),
+        extension
+    )
+}
+
+/// Downloads the font stylesheet and every font it references, which it then loads from
+/// `fonts/` next to it; Trunk copies that directory to the root of the site.
+fn vendor_font(vendor: &Path) -> Result<(), String> {
+    let css = fetch(FONT_URL, Some(FONT_USER_AGENT))?;
+    let mut css = String::from_utf8(css).map_err(|e| e.to_string())?;
+    let mut urls = Vec::new();
+    for (start, _) in css.match_indices("url(") {
+        let rest = &css[start + 4..];
+        let end = rest
+            .find(')')
+            .ok_or("unterminated url() in the font stylesheet")?;
+        urls.push(rest[..end].trim_matches(['"', '\'']).to_string());
+    }
+    for url in urls {
+        let name = file_name(&url);
+        write(&vendor.join("fonts").join(name), &fetch(&url, None)?)?;
+        css = css.replace(&url, &format!("fonts/{}", name));
+    }
+    write(&vendor.join(FONT_FILE), css.as_bytes())
+}
+
+fn vendor_index() -> Result<(), String> {
+    let mut index = fs::read_to_string(INDEX).map_err(|e| format!("{}: {}", INDEX, e))?;
+    for (remote, local) in INDEX_LINKS {
+        let Some(start) = index.find(remote) else {
+            continue;
+        };
+        // Lines without a replacement go away with their indentation and line break.
+        let (start, end) = match local.is_empty() {
+            true => (
+                index[..start].rfind('\n').map_or(0, |i| i + 1),
+                index[start..]
+                    .find('\n')
+                    .map_or(index.len(), |i| start + i + 1),
+            ),
+            false => (start, start + remote.len()),
+        };
+        index.replace_range(start..end, local);
+    }
+    write(Path::new(INDEX), index.as_bytes())
+}
+
+/// Downloads the remote images of the manifest and rewrites their URLs in place, so the
+/// formatting and comments of the manifest are kept.
+fn vendor_images() -> Result<bool, String> {
+    let mut source = fs::read_to_string(MANIFEST).map_err(|e| format!("{}: {}", MANIFEST, e))?;
+    let portfolio: Portfolio =
+        toml::from_str(&source).map_err(|e| format!("{}: {}", MANIFEST, e))?;
+    let mut ok = true;
+    // Local files and the image each holds, the local ones holding themselves.
+    let mut owners: HashMap<String, String> = portfolio
+        .images()
+        .into_iter()
+        .filter(|(_, image)| image.starts_with(IMAGES_DIR))
+        .map(|(_, image)| (image.to_string(), image.to_string()))
+        .collect();
+    for (path, url) in portfolio.images() {
+        if !url.starts_with("https://") && !url.starts_with("http://") {
+            continue;
+        }
+        let local = local_image(url, &owners);
+        if owners.get(&local).is_some_and(|owner| owner == url) {
+            continue;
+        }
+        owners.insert(local.clone(), url.to_string());
+        let downloaded = fetch(url, None).and_then(|bytes| write(Path::new(&local), &bytes));
+        match downloaded {
+            Ok(()) => {
+                eprintln!("{}: {}", path, local);
+                source = source.replace(&format!("\"{}\"", url), &format!("\"{}\"", local));
+            }
+            Err(e) => {
+                eprintln!("error: {}: {}: {}", path, url, e);
+                ok = false;
+            }
+        }
+    }
+    write(Path::new(MANIFEST), source.as_bytes())?;
+    Ok(ok)
+}
+
+fn main() {
+    let vendor = Path::new(VENDOR_DIR);
+    let mut failed = false;
+    match fetch(THEME_URL, None).and_then(|css| write(&vendor.join(THEME_FILE), &css)) {
+        Ok(()) => eprintln!("theme: {}/{}", VENDOR_DIR, THEME_FILE),
+        Err(e) => {
+            eprintln!("error: {}: {}", THEME_URL, e);
+            failed = true;
+        }
+    }
+    match vendor_font(vendor) {
+        Ok(()) => eprintln!("font: {}/{}", VENDOR_DIR, FONT_FILE),
+        Err(e) => {
+            eprintln!("error: {}: {}", FONT_URL, e);
+            failed = true;
+        }
+    }
+    // index.html only switches once both files are in place, so a failed run leaves a
+    // working site behind.
+    if !failed {
+        if let Err(e) = vendor_index() {
+            eprintln!("error: {}", e);
+            failed = true;
+        }
+    }
+    match vendor_images() {
+        Ok(ok) => failed |= !ok,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            failed = true;
+        }
+    }
+    if failed {
+        process::exit(1);
+    }
+}
@@ -0,0 +1,134 @@
+use crate::i18n::Locale;
+use crate::manifest::{Level, Skill};
+use std::collections::HashMap;
+use std::f64::consts::PI;
+use yew::prelude::*;
+
+const RADIUS: f64 = 170.0;
+/// More spokes than this make the labels overlap, so only the largest categories are drawn.
+const MAX_SPOKES: usize = 12;
+const BAR_WIDTH: f64 = 70.0;
+const BAR_GAP: f64 = 24.0;
+const HISTOGRAM_HEIGHT: f64 = 200.0;
+
+/// Average level per category, largest categories first.
+fn category_levels(skills: &[&Skill]) -> Vec<(String, f64)> {
+    let mut levels: HashMap<&str, (usize, usize)> = HashMap::new();
+    for skill in skills {
+        for category in &skill.categories {
+            let entry = levels.entry(category).or_default();
+            entry.0 += skill.level.0;
+            entry.1 += 1;
+        }
+    }
+    let mut levels: Vec<_> = levels.into_iter().collect();
+    levels.sort_by(|a, b| b.1 .1.cmp(&a.1 .1).then(a.0.cmp(b.0)));
+    levels
+        .into_iter()
+        .take(MAX_SPOKES)
+        .map(|(category, (sum, count))| (category.to_string(), sum as f64 / count as f64))
+        .collect()
+}
+
+/// Renders the average level of each category on its own spoke, levels 1 to 5 as rings.
+/// `select` receives the category whose point or label was clicked.
+pub fn radar(skills: &[&Skill], locale: Locale, select: Callback<String>) -> Html {
+    let levels = category_levels(skills);
+    if levels.is_empty() {
+        return html! {
+            <div class="subtitle is-6 has-text-centered gray4">{locale.t("skills.no_skills")}</div>
+        };
+    }
+    let point = |i: usize, r: f64| {
+        let angle = 2.0 * PI * i as f64 / levels.len() as f64 - PI / 2.0;
+        (r * angle.cos(), r * angle.sin())
+    };
+    let polygon = |r: &dyn Fn(usize) -> f64| {
+        (0..levels.len())
+            .map(|i| {
+                let (x, y) = point(i, r(i));
+                format!("{:.1},{:.1}", x, y)
+            })
+            .collect::<Vec<_>>()
+            .join(" ")
+    };
+    let area = polygon(&|i| levels[i].1 / 5.0 * RADIUS);
+
+    html! {
+        <svg class="chart" viewBox="-300 -230 600 460" role="img" aria-label={locale.t("skills.radar")}>
+            { for (1..=5).map(|ring| html! {
+                <polygon class="chart-grid" points={polygon(&|_| ring as f64 / 5.0 * RADIUS)}/>
+            }) }
+            { for (0..levels.len()).map(|i| {
+                let (x, y) = point(i, RADIUS);
+                html! {
+                    <line class="chart-grid" x1="0" y1="0" x2={x.to_string()} y2={y.to_string()}/>
+                }
+            }) }
+            <polygon class="chart-area" points={area}/>
+            { for levels.iter().enumerate().map(|(i, (category, level))| {
+                let (x, y) = point(i, level / 5.0 * RADIUS);
+                let (lx, ly) = point(i, RADIUS + 14.0);
+                let anchor = match lx {
+                    lx if lx > 1.0 => "start",
+                    lx if lx < -1.0 => "end",
+                    _ => "middle",
+                };
+                let onclick = {
+                    let select = select.clone();
+                    let category = category.clone();
+                    Callback::from(move |_| select.emit(category.clone()))
+                };
+                html! {
+                    <g class="chart-segment" {onclick}>
+                        <title>{format!("{}: {:.1}", category, level)}</title>
+                        <circle class="chart-point" cx={x.to_string()} cy={y.to_string()} r="5"/>
+                        <text class="chart-label" x={lx.to_string()} y={(ly + 4.0).to_string()}
+                            text-anchor={anchor}>{category}</text>
+                    </g>
+                }
+            }) }
+        </svg>
+    }
+}
+
+/// Renders the number of skills at every level as a bar.
+/// `select` receives the level of the bar that was clicked.
+pub fn histogram(skills: &[&Skill], locale: Locale, select: Callback<usize>) -> Html {
+    let mut counts = [0usize; 5];
+    for skill in skills {
+        if let Some(count) = counts.get_mut(skill.level.0.wrapping_sub(1)) {
+            *count += 1;
+        }
+    }
+    let max = counts.iter().copied().max().unwrap_or(0).max(1);
+    let width = 5.0 * (BAR_WIDTH + BAR_GAP) + BAR_GAP;
+
+    html! {
+        <svg class="chart" viewBox={format!("0 0 {} {}", width, HISTOGRAM_HEIGHT + 50.0)}
+            role="img" aria-label={locale.t("skills.histogram")}>
+            { for counts.iter().enumerate().map(|(i, count)| {
+                let level = i + 1;
+                let height = *count as f64 / max as f64 * (HISTOGRAM_HEIGHT - 20.0);
+                let x = BAR_GAP + i as f64 * (BAR_WIDTH + BAR_GAP);
+                let y = HISTOGRAM_HEIGHT - height;
+                let select = select.clone();
+                html! {
+                    <g class="chart-segment" onclick={Callback::from(move |_| select.emit(level))}>
+                        <title>{format!("{}: {}", Level(level), count)}</title>
+                        <rect class="chart-bar" x={x.to_string()} y={y.to_string()} rx="4"
+                            width={BAR_WIDTH.to_string()} height={height.to_string()}/>
+                        <text class="chart-label" x={(x + BAR_WIDTH / 2.0).to_string()}
+                            y={(y - 6.0).to_string()} text-anchor="middle">{count}</text>
+                        <text class="chart-label" x={(x + BAR_WIDTH / 2.0).to_string()}
+                            y={(HISTOGRAM_HEIGHT + 22.0).to_string()} text-anchor="middle">
+                            {Level(level).to_string()}
+                        </text>
+                    </g>
+                }
+            }) }
+            <line class="chart-grid" x1="0" x2={width.to_string()}
+                y1={HISTOGRAM_HEIGHT.to_string()} y2={HISTOGRAM_HEIGHT.to_string()}/>
+        </svg>
+    }
+}
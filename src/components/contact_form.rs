@@ -0,0 +1,437 @@
+use crate::i18n::Locale;
+use crate::manifest::{self, ContactDelivery};
+use gloo_net::http::Request;
+use gloo_timers::callback::Timeout;
+use serde::Serialize;
+use wasm_bindgen_futures::spawn_local;
+use web_sys::{HtmlInputElement, HtmlTextAreaElement};
+use yew::prelude::*;
+
+const NAME_MAX: usize = 100;
+const MESSAGE_MIN: usize = 10;
+const MESSAGE_MAX: usize = 2000;
+/// At most `RATE_LIMIT` messages are sent from one browser within `RATE_WINDOW` milliseconds.
+const RATE_LIMIT: usize = 3;
+const RATE_WINDOW: f64 = 60.0 * 60.0 * 1000.0;
+const SENT_STORAGE_KEY: &str = "contact.sent";
+const TOAST_MS: u32 = 6000;
+
+/// The delivery of the manifest, unless `PORTFOLIO_CONTACT_ENDPOINT` was set at build time,
+/// e.g. to try the form against `examples/contact_server.rs`.
+pub fn delivery(configured: Option<ContactDelivery>) -> Option<ContactDelivery> {
+    match option_env!("PORTFOLIO_CONTACT_ENDPOINT") {
+        Some(endpoint) => Some(ContactDelivery::Post {
+            endpoint: endpoint.to_string(),
+        }),
+        None => configured,
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Field {
+    Name,
+    Email,
+    Message,
+    /// The honeypot: hidden from people, so only bots fill it in.
+    Website,
+}
+
+#[derive(Clone, Default, Serialize)]
+struct Message {
+    name: String,
+    email: String,
+    message: String,
+}
+
+impl Message {
+    /// The locale key of the first problem with `field`, if any.
+    fn problem(&self, field: Field) -> Option<&'static str> {
+        let length = |s: &str| s.trim().chars().count();
+        match field {
+            Field::Name if length(&self.name) == 0 => Some("contact.name_required"),
+            Field::Name if length(&self.name) > NAME_MAX => Some("contact.name_too_long"),
+            Field::Email if !manifest::is_email(self.email.trim()) => Some("contact.email_invalid"),
+            Field::Message if length(&self.message) < MESSAGE_MIN => {
+                Some("contact.message_too_short")
+            }
+            Field::Message if length(&self.message) > MESSAGE_MAX => {
+                Some("contact.message_too_long")
+            }
+            _ => None,
+        }
+    }
+
+    fn is_valid(&self) -> bool {
+        [Field::Name, Field::Email, Field::Message]
+            .into_iter()
+            .all(|f| self.problem(f).is_none())
+    }
+
+    fn trimmed(&self) -> Message {
+        Message {
+            name: self.name.trim().to_string(),
+            email: self.email.trim().to_string(),
+            message: self.message.trim().to_string(),
+        }
+    }
+}
+
+/// What becomes of a valid message.
+#[derive(Debug, PartialEq)]
+enum Screening {
+    Deliver,
+    /// The honeypot was filled in, so the message is dropped.
+    Bot,
+    RateLimited,
+}
+
+/// Screens a message given the honeypot field and the recent sends from this browser.
+fn screen(honeypot: &str, recent_sends: &[f64]) -> Screening {
+    if !honeypot.is_empty() {
+        Screening::Bot
+    } else if recent_sends.len() >= RATE_LIMIT {
+        Screening::RateLimited
+    } else {
+        Screening::Deliver
+    }
+}
+
+/// The send times in `stored`, as kept in local storage, that are within the rate limit
+/// window at `now`.
+fn recent(stored: &str, now: f64) -> Vec<f64> {
+    stored
+        .split_whitespace()
+        .filter_map(|t| t.parse::<f64>().ok())
+        .filter(|t| now - t < RATE_WINDOW)
+        .collect()
+}
+
+/// Times of the messages sent from this browser within the rate limit window.
+fn recent_sends() -> Vec<f64> {
+    let stored = web_sys::window()
+        .and_then(|w| w.local_storage().ok()?)
+        .and_then(|s| s.get_item(SENT_STORAGE_KEY).ok()?)
+        .unwrap_or_default();
+    recent(&stored, js_sys::Date::now())
+}
+
+fn record_send() {
+    let mut sends = recent_sends();
+    sends.push(js_sys::Date::now());
+    let value: Vec<String> = sends.iter().map(|t| t.to_string()).collect();
+    if let Some(Ok(Some(storage))) = web_sys::window().map(|w| w.local_storage()) {
+        let _ = storage.set_item(SENT_STORAGE_KEY, &value.join(" "));
+    }
+}
+
+fn open_mail(email: &str, message: &Message) -> Result<(), String> {
+    let url = format!(
+        "mailto:{}?subject={}&body={}",
+        email,
+        js_sys::encode_uri_component(&format!("{} <{}>", message.name, message.email)),
+        js_sys::encode_uri_component(&message.message),
+    );
+    web_sys::window()
+        .ok_or("no window")?
+        .location()
+        .set_href(&url)
+        .map_err(|e| format!("{:?}", e))
+}
+
+async fn post(endpoint: &str, message: &Message) -> Result<(), String> {
+    let resp = Request::post(endpoint)
+        .json(message)
+        .map_err(|e| e.to_string())?
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+    match resp.ok() {
+        true => Ok(()),
+        false => Err(format!("{} {}", resp.status(), resp.status_text())),
+    }
+}
+
+#[derive(PartialEq, Properties)]
+pub struct Props {
+    pub delivery: ContactDelivery,
+    pub locale: Locale,
+}
+
+pub enum Msg {
+    Input(Field, String),
+    Submit(FocusEvent),
+    /// The locale key of the outcome, or the error behind a failure.
+    Sent(Result<&'static str, String>),
+    DismissToast,
+}
+
+struct Toast {
+    ok: bool,
+    text: String,
+    _timer: Timeout,
+}
+
+/// A contact form with validation, a honeypot field and a rate limit, which delivers
+/// the message by email or to an HTTP endpoint and reports the outcome in a toast.
+pub struct ContactForm {
+    message: Message,
+    honeypot: String,
+    /// Problems are only pointed out once sending was attempted.
+    attempted: bool,
+    sending: bool,
+    toast: Option<Toast>,
+}
+
+impl ContactForm {
+    fn toast(&mut self, ctx: &Context<Self>, ok: bool, text: String) {
+        let link = ctx.link().clone();
+        self.toast = Some(Toast {
+            ok,
+            text,
+            _timer: Timeout::new(TOAST_MS, move || link.send_message(Msg::DismissToast)),
+        });
+    }
+
+    fn send(&mut self, ctx: &Context<Self>) {
+        match screen(&self.honeypot, &recent_sends()) {
+            Screening::Deliver => {}
+            // Bots are told that it worked, so that they do not try again.
+            Screening::Bot => {
+                ctx.link().send_message(Msg::Sent(Ok("contact.sent")));
+                return;
+            }
+            Screening::RateLimited => {
+                let text = ctx.props().locale.t("contact.rate_limited");
+                self.toast(ctx, false, text);
+                return;
+            }
+        }
+        let message = self.message.trimmed();
+        match ctx.props().delivery.clone() {
+            ContactDelivery::Mailto { email } => ctx.link().send_message(Msg::Sent(
+                open_mail(&email, &message).map(|_| "contact.mail_opened"),
+            )),
+            ContactDelivery::Post { endpoint } => {
+                self.sending = true;
+                let link = ctx.link().clone();
+                spawn_local(async move {
+                    let sent = post(&endpoint, &message).await;
+                    link.send_message(Msg::Sent(sent.map(|_| "contact.sent")));
+                });
+            }
+        }
+    }
+}
+
+impl Component for ContactForm {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(_ctx: &Context<Self>) -> Self {
+        Self {
+            message: Message::default(),
+            honeypot: String::new(),
+            attempted: false,
+            sending: false,
+            toast: None,
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        let locale = ctx.props().locale;
+        match msg {
+            Msg::Input(field, value) => match field {
+                Field::Name => self.message.name = value,
+                Field::Email => self.message.email = value,
+                Field::Message => self.message.message = value,
+                Field::Website => self.honeypot = value,
+            },
+            Msg::Submit(e) => {
+                e.prevent_default();
+                self.attempted = true;
+                if self.message.is_valid() && !self.sending {
+                    self.send(ctx);
+                }
+            }
+            Msg::Sent(Ok(key)) => {
+                self.sending = false;
+                if self.honeypot.is_empty() {
+                    record_send();
+                }
+                self.message = Message::default();
+                self.attempted = false;
+                self.toast(ctx, true, locale.t(key));
+            }
+            Msg::Sent(Err(e)) => {
+                log::error!("could not send the message: {}", e);
+                self.sending = false;
+                self.toast(
+                    ctx,
+                    false,
+                    format!("{} ({})", locale.t("contact.failed"), e),
+                );
+            }
+            Msg::DismissToast => self.toast = None,
+        }
+        true
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let locale = ctx.props().locale;
+        let link = ctx.link();
+        let problem = |field: Field| match self.attempted {
+            true => self.message.problem(field),
+            false => None,
+        };
+        let help = |field: Field| match problem(field) {
+            Some(key) => html! { <p class="help is-danger">{locale.t(key)}</p> },
+            None => html! {},
+        };
+        let input_class = |field: Field| match problem(field) {
+            Some(_) => classes!("input", "is-small", "is-danger"),
+            None => classes!("input", "is-small"),
+        };
+        let input = |field: Field| {
+            link.callback(move |e: InputEvent| {
+                Msg::Input(field, e.target_unchecked_into::<HtmlInputElement>().value())
+            })
+        };
+        let length = self.message.message.trim().chars().count();
+        let counter_class = match length > MESSAGE_MAX {
+            true => "has-text-danger",
+            false => "gray4",
+        };
+        let textarea_class = match problem(Field::Message) {
+            Some(_) => classes!("textarea", "is-small", "is-danger"),
+            None => classes!("textarea", "is-small"),
+        };
+
+        html! {
+            <form class="contactform" novalidate=true onsubmit={link.callback(Msg::Submit)}>
+                <div class="field">
+                    <input class={input_class(Field::Name)} type="text" autocomplete="name"
+                        placeholder={locale.t("contact.name")} aria-label={locale.t("contact.name")}
+                        value={self.message.name.clone()} oninput={input(Field::Name)}/>
+                    {help(Field::Name)}
+                </div>
+                <div class="field">
+                    <input class={input_class(Field::Email)} type="email" autocomplete="email"
+                        placeholder={locale.t("contact.your_email")}
+                        aria-label={locale.t("contact.your_email")}
+                        value={self.message.email.clone()} oninput={input(Field::Email)}/>
+                    {help(Field::Email)}
+                </div>
+                <div class="contactform-website" aria-hidden="true">
+                    <input type="text" name="website" tabindex="-1" autocomplete="off"
+                        value={self.honeypot.clone()} oninput={input(Field::Website)}/>
+                </div>
+                <div class="field">
+                    <textarea class={textarea_class} rows="6"
+                        placeholder={locale.t("contact.message")}
+                        aria-label={locale.t("contact.message")}
+                        value={self.message.message.clone()}
+                        oninput={link.callback(|e: InputEvent| Msg::Input(
+                            Field::Message,
+                            e.target_unchecked_into::<HtmlTextAreaElement>().value(),
+                        ))}/>
+                    <p class={classes!("help", "has-text-right", counter_class)}>
+                        {format!("{} / {}", length, MESSAGE_MAX)}
+                    </p>
+                    {help(Field::Message)}
+                </div>
+                <button type="submit" disabled={self.sending}
+                    class={classes!("button", "is-small", "is-white", self.sending.then_some("is-loading"))}>
+                    {locale.t("contact.send")}
+                </button>
+                if let Some(toast) = &self.toast {
+                    <div role="status" class={classes!("notification", "contacttoast",
+                        if toast.ok { "is-success" } else { "is-danger" })}>
+                        <button type="button" class="delete" aria-label={locale.t("contact.close")}
+                            onclick={link.callback(|_| Msg::DismissToast)}/>
+                        {&toast.text}
+                    </div>
+                }
+            </form>
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(name: &str, email: &str, message: &str) -> Message {
+        Message {
+            name: name.to_string(),
+            email: email.to_string(),
+            message: message.to_string(),
+        }
+    }
+
+    #[test]
+    fn checks_every_field() {
+        let valid = message("Ana", "ana@example.com", "Hello, are you available?");
+        assert!(valid.is_valid());
+        for field in [Field::Name, Field::Email, Field::Message, Field::Website] {
+            assert_eq!(valid.problem(field), None);
+        }
+
+        let problems =
+            |m: &Message| [Field::Name, Field::Email, Field::Message].map(|f| m.problem(f));
+        assert_eq!(
+            problems(&message(" ", "ana@example", "Hello    ")),
+            [
+                Some("contact.name_required"),
+                Some("contact.email_invalid"),
+                Some("contact.message_too_short"),
+            ]
+        );
+        assert_eq!(
+            problems(&message(&"a".repeat(101), "ana", &"a".repeat(2001))),
+            [
+                Some("contact.name_too_long"),
+                Some("contact.email_invalid"),
+                Some("contact.message_too_long"),
+            ]
+        );
+        assert!(!message("Ana", "ana@example", "Hello, are you available?").is_valid());
+    }
+
+    #[test]
+    fn lengths_are_in_characters_without_the_surrounding_space() {
+        let m = message(&"ș".repeat(100), " ana@example.com ", "  ăîșțâăîșțâ  ");
+        assert!(m.is_valid());
+        assert_eq!(m.trimmed().email, "ana@example.com");
+        assert_eq!(m.trimmed().message, "ăîșțâăîșțâ");
+        assert_eq!(
+            message("Ana", "ana@example.com", &" a".repeat(5)).problem(Field::Message),
+            Some("contact.message_too_short")
+        );
+    }
+
+    #[test]
+    fn honeypot_drops_the_message_before_the_rate_limit() {
+        assert_eq!(screen("", &[]), Screening::Deliver);
+        assert_eq!(screen("", &[1.0, 2.0]), Screening::Deliver);
+        assert_eq!(screen("", &[1.0, 2.0, 3.0]), Screening::RateLimited);
+        assert_eq!(screen("https://spam.example", &[]), Screening::Bot);
+        assert_eq!(screen("x", &[1.0, 2.0, 3.0]), Screening::Bot);
+    }
+
+    #[test]
+    fn only_sends_within_the_window_count() {
+        let now = 10.0 * RATE_WINDOW;
+        let stored = format!(
+            "{} {} junk {} {}",
+            now - RATE_WINDOW,
+            now - RATE_WINDOW + 1.0,
+            now - 1000.0,
+            now
+        );
+        assert_eq!(
+            recent(&stored, now),
+            [now - RATE_WINDOW + 1.0, now - 1000.0, now]
+        );
+        assert!(recent("", now).is_empty());
+    }
+}
@@ -0,0 +1,31 @@
+use crate::i18n::Locale;
+use crate::manifest::Content;
+use yew::prelude::*;
+
+/// Renders the skeleton or the error state for content that is not ready yet.
+/// Components call this before touching the portfolio data.
+pub fn placeholder(
+    content: &Content,
+    locale: Locale,
+    rows: usize,
+    retry: Callback<MouseEvent>,
+) -> Html {
+    match content {
+        Content::Failed(error) => html! {
+            <div class="box has-text-centered contenterror">
+                <div class="subtitle is-5">{locale.t("content.error")}</div>
+                <p class="subtitle is-7 gray4">{error}</p>
+                <button class="button is-white is-outlined is-small" onclick={retry}>
+                    {locale.t("content.retry")}
+                </button>
+            </div>
+        },
+        _ => html! {
+            <div class="container" style="max-width: 1030px" aria-busy="true">
+                { for (0..rows).map(|i| html! {
+                    <div class="skeleton" style={format!("width: {}%", 100 - (i % 3) * 15)}></div>
+                }) }
+            </div>
+        },
+    }
+}
@@ -0,0 +1,226 @@
+use crate::manifest::Skill;
+use gloo_timers::callback::Timeout;
+use js_sys::{Array, JsString};
+use wasm_bindgen::JsCast;
+use web_sys::{Blob, BlobPropertyBag, HtmlAnchorElement, Url};
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Format {
+    Csv,
+    Json,
+    Markdown,
+}
+
+impl Format {
+    pub const ALL: [Format; 3] = [Format::Csv, Format::Json, Format::Markdown];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Format::Csv => "CSV",
+            Format::Json => "JSON",
+            Format::Markdown => "Markdown",
+        }
+    }
+
+    fn file_name(self) -> &'static str {
+        match self {
+            Format::Csv => "skills.csv",
+            Format::Json => "skills.json",
+            Format::Markdown => "skills.md",
+        }
+    }
+
+    fn mime(self) -> &'static str {
+        match self {
+            Format::Csv => "text/csv;charset=utf-8",
+            Format::Json => "application/json;charset=utf-8",
+            Format::Markdown => "text/markdown;charset=utf-8",
+        }
+    }
+}
+
+/// The columns of an exported row, as text.
+fn cells(skill: &Skill) -> [String; 7] {
+    [
+        skill.categories.join(", "),
+        skill.technology.clone(),
+        skill.level.0.to_string(),
+        skill.years.map(|y| y.to_string()).unwrap_or_default(),
+        skill.last_used.map(|y| y.to_string()).unwrap_or_default(),
+        skill.trend.map(|t| t.key().to_string()).unwrap_or_default(),
+        skill.note.clone(),
+    ]
+}
+
+/// Named like the manifest fields, so that the files do not depend on the page language.
+const HEADERS: [&str; 7] = [
+    "category",
+    "technology",
+    "level",
+    "years",
+    "last_used",
+    "trend",
+    "note",
+];
+
+fn csv_field(field: &str) -> String {
+    match field.contains([',', '"', '\n', '\r']) {
+        true => format!("\"{}\"", field.replace('"', "\"\"")),
+        false => field.to_string(),
+    }
+}
+
+fn markdown_cell(cell: &str) -> String {
+    cell.replace('|', "\\|").replace('\n', " ")
+}
+
+/// Writes the rows in `format`, after a header listing the active filters, given as
+/// (label, value) pairs. In CSV the header is `#` comment lines before the table.
+pub fn render(format: Format, skills: &[&Skill], filters: &[(&str, String)]) -> String {
+    match format {
+        Format::Csv => {
+            let mut out: String = filters
+                .iter()
+                .map(|(label, value)| {
+                    format!("# {}: {}\n", label, value.replace(['\r', '\n'], " "))
+                })
+                .collect();
+            out.push_str(&HEADERS.join(","));
+            out.push('\n');
+            for row in skills.iter().map(|s| cells(s)) {
+                let row: Vec<_> = row.iter().map(|c| csv_field(c)).collect();
+                out.push_str(&row.join(","));
+                out.push('\n');
+            }
+            out
+        }
+        Format::Json => {
+            let filters: serde_json::Map<_, _> = filters
+                .iter()
+                .map(|(label, value)| (label.to_string(), value.clone().into()))
+                .collect();
+            let skills: Vec<_> = skills
+                .iter()
+                .map(|s| {
+                    serde_json::json!({
+                        "categories": s.categories,
+                        "technology": s.technology,
+                        "level": s.level.0,
+                        "years": s.years,
+                        "last_used": s.last_used,
+                        "trend": s.trend,
+                        "note": s.note,
+                    })
+                })
+                .collect();
+            serde_json::to_string_pretty(&serde_json::json!({
+                "filters": filters,
+                "skills": skills,
+            }))
+            .unwrap()
+        }
+        Format::Markdown => {
+            let mut out = String::new();
+            for (label, value) in filters {
+                out.push_str(&format!("> {}: {}  \n", label, markdown_cell(value)));
+            }
+            if !filters.is_empty() {
+                out.push('\n');
+            }
+            out.push_str(&format!("| {} |\n", HEADERS.join(" | ")));
+            out.push_str(&format!("|{}\n", " --- |".repeat(HEADERS.len())));
+            for skill in skills {
+                let row: Vec<_> = cells(skill).iter().map(|c| markdown_cell(c)).collect();
+                out.push_str(&format!("| {} |\n", row.join(" | ")));
+            }
+            out
+        }
+    }
+}
+
+/// Saves `contents` through a temporary Blob URL, without a round trip to a server.
+pub fn download(format: Format, contents: &str) {
+    let options = BlobPropertyBag::new();
+    options.set_type(format.mime());
+    let saved =
+        Blob::new_with_str_sequence_and_options(&Array::of1(&JsString::from(contents)), &options)
+            .and_then(|blob| Url::create_object_url_with_blob(&blob))
+            .and_then(|url| {
+                let document = web_sys::window()
+                    .and_then(|w| w.document())
+                    .ok_or("no document")?;
+                let link: HtmlAnchorElement = document.create_element("a")?.unchecked_into();
+                link.set_href(&url);
+                link.set_download(format.file_name());
+                link.click();
+                // The download starts asynchronously, so the URL has to outlive this call.
+                Timeout::new(1000, move || {
+                    let _ = Url::revoke_object_url(&url);
+                })
+                .forget();
+                Ok(())
+            });
+    if let Err(e) = saved {
+        log::error!("could not export the skills: {:?}", e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::manifest::Level;
+
+    fn skill(technology: &str, note: &str) -> Skill {
+        Skill {
+            categories: vec!["Programming".to_string(), "Web".to_string()],
+            technology: technology.to_string(),
+            level: Level(4),
+            note: note.to_string(),
+            years: Some(3),
+            last_used: None,
+            trend: None,
+            translations: Default::default(),
+        }
+    }
+
+    #[test]
+    fn csv_starts_with_the_filters_as_comments() {
+        let rust = skill("Rust", "plain");
+        let filters = [
+            ("search", "rust, \"web\"".to_string()),
+            ("categories", "Programming\nWeb".to_string()),
+            ("sort", "level, technology".to_string()),
+        ];
+        assert_eq!(
+            render(Format::Csv, &[&rust], &filters),
+            "# search: rust, \"web\"\n\
+             # categories: Programming Web\n\
+             # sort: level, technology\n\
+             category,technology,level,years,last_used,trend,note\n\
+             \"Programming, Web\",Rust,4,3,,,plain\n"
+        );
+        assert!(render(Format::Csv, &[&rust], &[]).starts_with("category,"));
+    }
+
+    #[test]
+    fn csv_quotes_separators_quotes_and_line_breaks() {
+        assert_eq!(csv_field("plain"), "plain");
+        assert_eq!(csv_field("a,b"), "\"a,b\"");
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+        assert_eq!(csv_field("two\nlines"), "\"two\nlines\"");
+        assert_eq!(csv_field("two\rlines"), "\"two\rlines\"");
+    }
+
+    #[test]
+    fn json_and_markdown_keep_the_filters() {
+        let rust = skill("Rust", "a | b");
+        let filters = [("sort", "level".to_string())];
+        let json: serde_json::Value =
+            serde_json::from_str(&render(Format::Json, &[&rust], &filters)).unwrap();
+        assert_eq!(json["filters"]["sort"], "level");
+        assert_eq!(json["skills"][0]["technology"], "Rust");
+        let markdown = render(Format::Markdown, &[&rust], &filters);
+        assert!(markdown.starts_with("> sort: level  \n\n| category |"));
+        assert!(markdown.contains("| a \\| b |"));
+    }
+}
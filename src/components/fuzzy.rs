@@ -0,0 +1,213 @@
+use yew::prelude::*;
+
+/// How well a query matched a list of weighted fields, with the matched character
+/// positions of every field (in chars, not bytes) for highlighting.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Ranked {
+    pub score: u32,
+    pub positions: Vec<Vec<usize>>,
+}
+
+pub struct Match {
+    pub score: u32,
+    pub positions: Vec<usize>,
+}
+
+/// Best of an exact substring, an in-order subsequence ("kubernets") and a word
+/// within a small edit distance ("wiregaurd"), in that order of preference.
+pub fn find(query: &str, text: &str) -> Option<Match> {
+    let q = lower(query);
+    let t = lower(text);
+    substring(&q, &t)
+        .or_else(|| subsequence(&q, &t))
+        .or_else(|| typo(&q, &t))
+}
+
+/// Case-insensitive substring match, without any tolerance for typos.
+pub fn exact(query: &str, text: &str) -> Option<Match> {
+    substring(&lower(query), &lower(text))
+}
+
+pub fn lower(s: &str) -> Vec<char> {
+    s.chars()
+        .map(|c| c.to_lowercase().next().unwrap_or(c))
+        .collect()
+}
+
+fn word_start(t: &[char], i: usize) -> bool {
+    i == 0 || !t[i - 1].is_alphanumeric()
+}
+
+fn substring(q: &[char], t: &[char]) -> Option<Match> {
+    let starts: Vec<usize> = (0..=t.len().checked_sub(q.len())?)
+        .filter(|i| t[*i..*i + q.len()] == *q)
+        .collect();
+    let start = *starts
+        .iter()
+        .find(|i| word_start(t, **i))
+        .or(starts.first())?;
+    let mut score = 100;
+    if word_start(t, start) {
+        score += 20;
+    }
+    if q.len() == t.len() {
+        score += 30;
+    }
+    Some(Match {
+        score,
+        positions: (start..start + q.len()).collect(),
+    })
+}
+
+fn subsequence(q: &[char], t: &[char]) -> Option<Match> {
+    if q.len() < 3 {
+        return None;
+    }
+    let mut positions = Vec::with_capacity(q.len());
+    let mut next = 0;
+    for c in q {
+        let i = next + t[next..].iter().position(|tc| tc == c)?;
+        positions.push(i);
+        next = i + 1;
+    }
+    // Scattered letters are a coincidence rather than a misspelling.
+    let gaps = positions[positions.len() - 1] - positions[0] + 1 - q.len();
+    if gaps > q.len() / 3 + 1 {
+        return None;
+    }
+    Some(Match {
+        score: 70u32.saturating_sub(10 * gaps as u32),
+        positions,
+    })
+}
+
+fn typo(q: &[char], t: &[char]) -> Option<Match> {
+    if q.len() < 4 {
+        return None;
+    }
+    let allowed = if q.len() <= 5 { 1 } else { 2 };
+    let mut best: Option<(usize, usize, usize)> = None;
+    let mut start = 0;
+    while start < t.len() {
+        let end = start
+            + t[start..]
+                .iter()
+                .position(|c| !c.is_alphanumeric())
+                .unwrap_or(t.len() - start);
+        if end > start {
+            let distance = edit_distance(q, &t[start..end]);
+            if distance <= allowed && best.is_none_or(|(d, _, _)| distance < d) {
+                best = Some((distance, start, end));
+            }
+        }
+        start = end + 1;
+    }
+    best.map(|(distance, start, end)| Match {
+        score: 40 - 10 * distance as u32,
+        positions: (start..end).collect(),
+    })
+}
+
+/// Levenshtein distance that also counts a swap of two neighbours as one edit.
+fn edit_distance(a: &[char], b: &[char]) -> usize {
+    let mut rows = vec![(0..=b.len()).collect::<Vec<_>>()];
+    for i in 1..=a.len() {
+        let mut row = vec![i; b.len() + 1];
+        for j in 1..=b.len() {
+            let cost = (a[i - 1] != b[j - 1]) as usize;
+            row[j] = (rows[i - 1][j] + 1)
+                .min(row[j - 1] + 1)
+                .min(rows[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                row[j] = row[j].min(rows[i - 2][j - 2] + 1);
+            }
+        }
+        rows.push(row);
+    }
+    rows[a.len()][b.len()]
+}
+
+/// Renders `text` with the characters at `positions` wrapped in `<mark>`.
+pub fn highlight(text: &str, positions: &[usize]) -> Html {
+    if positions.is_empty() {
+        return html! { {text} };
+    }
+    let mut parts: Vec<(bool, String)> = Vec::new();
+    for (i, c) in text.chars().enumerate() {
+        let marked = positions.binary_search(&i).is_ok();
+        match parts.last_mut() {
+            Some((m, part)) if *m == marked => part.push(c),
+            _ => parts.push((marked, c.to_string())),
+        }
+    }
+    html! {
+        { for parts.into_iter().map(|(marked, part)| match marked {
+            true => html! { <mark>{part}</mark> },
+            false => html! { {part} },
+        }) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn score(query: &str, text: &str) -> Option<u32> {
+        find(query, text).map(|m| m.score)
+    }
+
+    #[test]
+    fn substrings_score_by_where_they_match() {
+        assert_eq!(score("rust", "Rust"), Some(150));
+        assert_eq!(score("rust", "Rust async"), Some(120));
+        assert_eq!(score("ust", "Rust"), Some(100));
+        // The occurrence at the start of a word is the one highlighted.
+        let m = exact("NET", "Internet Networking").unwrap();
+        assert_eq!((m.score, m.positions), (120, vec![9, 10, 11]));
+    }
+
+    #[test]
+    fn subsequences_tolerate_missing_letters() {
+        let m = find("kubernets", "Kubernetes").unwrap();
+        assert_eq!(m.score, 60);
+        assert_eq!(m.positions, [0, 1, 2, 3, 4, 5, 6, 7, 9]);
+        assert_eq!(score("ks", "Kubernetes"), None);
+        assert_eq!(score("abc", "a----b----c"), None);
+    }
+
+    #[test]
+    fn typos_match_a_whole_word() {
+        let m = find("wiregaurd", "WireGuard VPN").unwrap();
+        assert_eq!(m.score, 30);
+        assert_eq!(m.positions, (0..9).collect::<Vec<_>>());
+        assert_eq!(score("dokcer", "Docker Compose"), Some(30));
+        assert_eq!(score("postgrse", "PostgreSQL"), None);
+        assert_eq!(score("gti", "Git"), None);
+        assert!(exact("wiregaurd", "WireGuard").is_none());
+    }
+
+    #[test]
+    fn better_matches_score_higher() {
+        let exact = score("docker", "Docker").unwrap();
+        let prefix = score("dock", "Docker").unwrap();
+        let inner = score("ocker", "Docker").unwrap();
+        let subsequence = score("dckr", "Docker").unwrap();
+        let typo = score("dcoker", "Docker").unwrap();
+        assert!(exact > prefix && prefix > inner && inner > subsequence && subsequence > typo);
+    }
+
+    #[test]
+    fn edit_distance_counts_swaps_once() {
+        assert_eq!(edit_distance(&lower("kitten"), &lower("sitting")), 3);
+        assert_eq!(edit_distance(&lower("gaurd"), &lower("guard")), 1);
+        assert_eq!(edit_distance(&lower(""), &lower("go")), 2);
+    }
+
+    #[test]
+    fn positions_are_in_chars() {
+        let m = find("ște", "Ștefan Ștef").unwrap();
+        assert_eq!((m.score, m.positions), (120, vec![0, 1, 2]));
+        let m = find("fan", "Ștefan").unwrap();
+        assert_eq!(m.positions, [3, 4, 5]);
+    }
+}
@@ -0,0 +1,120 @@
+use crate::components::picture::Picture;
+use crate::i18n::Locale;
+use crate::manifest::Screenshot;
+use std::rc::Rc;
+use web_sys::HtmlElement;
+use yew::prelude::*;
+
+#[derive(PartialEq, Properties)]
+pub struct Props {
+    pub pictures: Rc<Vec<Screenshot>>,
+    /// Alternative text of the pictures without a caption.
+    pub title: String,
+    pub locale: Locale,
+}
+
+pub enum Msg {
+    Open(usize),
+    Close,
+    Step(isize),
+    Key(KeyboardEvent),
+}
+
+/// Thumbnails that open full size in a lightbox, where the arrows (buttons or keys)
+/// move between the pictures and Escape closes it.
+pub struct Gallery {
+    open: Option<usize>,
+    lightbox: NodeRef,
+}
+
+impl Component for Gallery {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(_ctx: &Context<Self>) -> Self {
+        Self {
+            open: None,
+            lightbox: NodeRef::default(),
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        let count = ctx.props().pictures.len() as isize;
+        let step = |open: Option<usize>, by: isize| {
+            open.map(|i| (i as isize + by).rem_euclid(count.max(1)) as usize)
+        };
+        self.open = match msg {
+            Msg::Open(i) => Some(i),
+            Msg::Close => None,
+            Msg::Step(by) => step(self.open, by),
+            Msg::Key(e) => match e.key().as_str() {
+                "Escape" => None,
+                "ArrowLeft" => step(self.open, -1),
+                "ArrowRight" => step(self.open, 1),
+                _ => return false,
+            },
+        };
+        true
+    }
+
+    fn changed(&mut self, _ctx: &Context<Self>) -> bool {
+        self.open = None;
+        true
+    }
+
+    fn rendered(&mut self, _ctx: &Context<Self>, _first_render: bool) {
+        // Focused so that the arrow keys and Escape reach it.
+        if let Some(lightbox) = self.lightbox.cast::<HtmlElement>() {
+            let _ = lightbox.focus();
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let Props {
+            pictures,
+            title,
+            locale,
+        } = ctx.props();
+        let link = ctx.link();
+        let alt = |p: &Screenshot| match p.caption.is_empty() {
+            true => title.clone(),
+            false => p.caption.clone(),
+        };
+        html! {
+            <div class="gallery">
+                { for pictures.iter().enumerate().map(|(i, p)| html! {
+                    <a class="gallery-thumb" onclick={link.callback(move |_| Msg::Open(i))}>
+                        <Picture src={p.url.clone()} alt={alt(p)} sizes="240px"/>
+                    </a>
+                }) }
+                if let Some(p) = self.open.and_then(|i| pictures.get(i)) {
+                    <div class="modal is-active" tabindex="-1" ref={self.lightbox.clone()}
+                        onkeydown={link.callback(Msg::Key)}>
+                        <div class="modal-background" onclick={link.callback(|_| Msg::Close)}/>
+                        <div class="modal-content has-text-centered">
+                            <Picture class="gallery-full" src={p.url.clone()} alt={alt(p)} eager=true/>
+                            if !p.caption.is_empty() {
+                                <p class="gray4">{&p.caption}</p>
+                            }
+                            if pictures.len() > 1 {
+                                <div class="buttons is-centered gallery-nav">
+                                    <button class="button is-small is-black"
+                                        aria-label={locale.t("projects.previous")}
+                                        onclick={link.callback(|_| Msg::Step(-1))}>{"❮"}</button>
+                                    <span class="gray4">
+                                        {format!("{} / {}", self.open.unwrap_or(0) + 1, pictures.len())}
+                                    </span>
+                                    <button class="button is-small is-black"
+                                        aria-label={locale.t("projects.next")}
+                                        onclick={link.callback(|_| Msg::Step(1))}>{"❯"}</button>
+                                </div>
+                            }
+                        </div>
+                        <button class="modal-close is-large" aria-label={locale.t("projects.close")}
+                            onclick={link.callback(|_| Msg::Close)}/>
+                    </div>
+                }
+            </div>
+        }
+    }
+}
@@ -1,53 +1,13 @@
-use gloo_timers::future::TimeoutFuture;
-use wasm_bindgen_futures::spawn_local;
+use crate::components::markdown;
+use crate::components::skills::SkillQuery;
+use crate::components::transition::{Ticket, Transition, JOB_TEXT_EXIT_MS};
+use crate::i18n::Locale;
+use crate::manifest::{JobMeta, YearMonth};
+use crate::Route;
+use std::rc::Rc;
+use web_sys::{Element, ScrollBehavior, ScrollIntoViewOptions};
 use yew::prelude::*;
-
-#[derive(PartialEq)]
-pub enum Job {
-    GheoTech,
-    Kape,
-    Vodafone,
-    IBM,
-    Freelance,
-}
-
-struct JobMeta {
-    title: String,
-    company: String,
-    start: String,
-    end: String,
-    story: String,
-    tech: String,
-    resp: String,
-}
-
-impl From<&Job> for JobMeta {
-    fn from(j: &Job) -> Self {
-        macro_rules! match_j {
-            ( $( $job_key:ident => {
-                    $title:expr, $company:expr, $start: expr, $end: expr, $file:expr
-                }, )+
-            ) => { match j {
-                $( Job::$job_key => JobMeta {
-                    title: $title.to_string(),
-                    company: $company.to_string(),
-                    start: $start.to_string(),
-                    end: $end.to_string(),
-                    story: include_str!(concat!("data/", $file, "_story.html")).to_string(),
-                    tech: include_str!(concat!("data/", $file, "_tech.html")).to_string(),
-                    resp: include_str!(concat!("data/", $file, "_resp.html")).to_string(),
-                }, )+
-            }  };
-        }
-        match_j!(
-            GheoTech => { "Engineer, Consultant", "Gheorghe Technologies SRL", "08/2022", "present", "gheotech" },
-            Kape => { "Infrastructure Architect", "Kape", "09/2018", "07/2022", "kape" },
-            Vodafone => { "Private Cloud Engineer", "Vodafone", "04/2015", "09/2018", "vodafone" },
-            IBM => { "Java Dev, Sysadmin", "IBM", "05/2014", "04/2015", "ibm" },
-            Freelance => { "Freelancer", "Multiple Projects", "2010", "2015", "freelance" },
-        )
-    }
-}
+use yew_router::prelude::*;
 
 #[derive(Clone, PartialEq, Debug)]
 pub enum TextVisibility {
@@ -58,12 +18,12 @@ pub enum TextVisibility {
 }
 
 impl TextVisibility {
-    fn to_string(&self) -> String {
+    fn label(&self, locale: Locale) -> String {
         match self {
             TextVisibility::None => String::new(),
-            TextVisibility::Story => "story".to_string(),
-            TextVisibility::Tech => "tech".to_string(),
-            TextVisibility::Resp => "scope".to_string(),
+            TextVisibility::Story => locale.t("job.story"),
+            TextVisibility::Tech => locale.t("job.tech"),
+            TextVisibility::Resp => locale.t("job.scope"),
         }
     }
 }
@@ -71,6 +31,7 @@ impl TextVisibility {
 pub enum Msg {
     Buttons,
     Text(TextVisibility),
+    Switched(Ticket),
 }
 
 impl TextVisibility {
@@ -95,14 +56,31 @@ impl TextVisibility {
 }
 
 pub struct JobCard {
-    job: JobMeta,
     text: TextVisibility,
+    /// Hides the open text before showing another one.
+    switch: Transition<TextVisibility>,
     menu: bool,
+    focus: u32,
+    node: NodeRef,
 }
 
 #[derive(PartialEq, Properties)]
 pub struct Props {
-    pub job: Job,
+    pub job: Rc<JobMeta>,
+    pub locale: Locale,
+    /// Raising this value scrolls to the card and opens it on its story.
+    #[prop_or_default]
+    pub focus: u32,
+}
+
+impl JobCard {
+    fn scroll_into_view(&self) {
+        if let Some(card) = self.node.cast::<Element>() {
+            let options = ScrollIntoViewOptions::new();
+            options.set_behavior(ScrollBehavior::Smooth);
+            card.scroll_into_view_with_scroll_into_view_options(&options);
+        }
+    }
 }
 
 impl Component for JobCard {
@@ -110,53 +88,87 @@ impl Component for JobCard {
     type Properties = Props;
 
     fn create(ctx: &Context<Self>) -> Self {
+        let focused = ctx.props().focus > 0;
         Self {
-            job: JobMeta::from(&ctx.props().job),
-            text: TextVisibility::None,
-            menu: false,
+            text: match focused {
+                true => TextVisibility::Story,
+                false => TextVisibility::None,
+            },
+            switch: Transition::default(),
+            menu: focused,
+            focus: ctx.props().focus,
+            node: NodeRef::default(),
+        }
+    }
+
+    fn changed(&mut self, ctx: &Context<Self>) -> bool {
+        if ctx.props().focus > self.focus {
+            self.focus = ctx.props().focus;
+            self.menu = true;
+            self.text = TextVisibility::Story;
+            self.switch.cancel();
+            self.scroll_into_view();
+        }
+        true
+    }
+
+    fn rendered(&mut self, _ctx: &Context<Self>, first_render: bool) {
+        // A card created focused, such as the job linked from the skills page.
+        if first_render && self.focus > 0 {
+            self.scroll_into_view();
         }
     }
 
     fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
         match msg {
             Msg::Text(text_visibility) => {
-                self.text = match text_visibility == self.text {
-                    true => TextVisibility::None,
-                    false => match self.text {
-                        TextVisibility::None => text_visibility,
-                        _ => {
-                            let link = ctx.link().clone();
-                            spawn_local(async move {
-                                TimeoutFuture::new(1000).await;
-                                link.send_message(Msg::Text(text_visibility.clone()));
-                            });
-                            TextVisibility::None
-                        }
-                    },
-                };
+                if self.switch.target() == Some(&text_visibility) {
+                    self.switch.cancel();
+                } else if self.text == text_visibility {
+                    self.text = TextVisibility::None;
+                } else if self.text == TextVisibility::None && !self.switch.is_exiting() {
+                    self.text = text_visibility;
+                } else {
+                    self.text = TextVisibility::None;
+                    self.switch.start_after(
+                        text_visibility,
+                        JOB_TEXT_EXIT_MS,
+                        ctx.link(),
+                        Msg::Switched,
+                    );
+                }
             }
+            Msg::Switched(ticket) => match self.switch.finish(ticket) {
+                Some(text_visibility) => self.text = text_visibility,
+                None => return false,
+            },
             Msg::Buttons => self.menu = !self.menu,
         }
         true
     }
 
     fn view(&self, ctx: &Context<Self>) -> Html {
-        let story_div = gloo_utils::document().create_element("div").unwrap();
-        story_div.set_inner_html(&self.job.story);
-        let tech_div = gloo_utils::document().create_element("div").unwrap();
-        tech_div.set_inner_html(&self.job.tech);
-        let resp_div = gloo_utils::document().create_element("div").unwrap();
-        resp_div.set_inner_html(&self.job.resp);
+        let job = &ctx.props().job;
+        let locale = ctx.props().locale;
+        let end = match job.end {
+            Some(end) => end.to_string(),
+            None => locale.t("job.present"),
+        };
+        let tenure = locale.duration(
+            job.start
+                .months_until(job.end.unwrap_or_else(YearMonth::today)),
+        );
 
         let menu_class = match self.menu {
             true => "jobmenu-visible",
             false => "jobmenu-hidden",
         };
 
-        let menu_button_weight = |v: TextVisibility| match self.text == v {
-            true => "jobbuttonbolder",
-            false => "jobbutton",
-        };
+        let menu_button_weight =
+            |v: TextVisibility| match *self.switch.target().unwrap_or(&self.text) == v {
+                true => "jobbuttonbolder",
+                false => "jobbutton",
+            };
 
         fn text_class(active: bool) -> &'static str {
             match active {
@@ -173,28 +185,45 @@ impl Component for JobCard {
                             class={classes!("button", "is-white", "is-outlined",
                             menu_button_weight(TextVisibility::$title))}
                             onclick={ctx.link().callback(|_| Msg::Text(TextVisibility::$title))}>
-                        {TextVisibility::$title.to_string()}</button>
+                        {TextVisibility::$title.label(locale)}</button>
                     </div>
                 }
             }};
         }
 
         macro_rules! show_text {
-            ($title:expr, $func:expr, $div:expr) => {{
+            ($title:expr, $func:expr, $text:expr $(, $extra:expr)?) => {{
                 html! {
                     <div class={classes!("jobtext", text_class(self.text.$func && self.menu))}>
-                        {Html::VRef($div.into())}
+                        {markdown::render(&$text)}
+                        $({$extra})?
                     </div>
                 }
             }};
         }
 
+        let tech_chips = html! {
+            <div class="tags techchips">
+                { for job.skills.iter().map(|technology| html! {
+                    <Link<Route, SkillQuery> classes={classes!("tag", "is-black")}
+                        to={Route::Skills.localized(locale)}
+                        query={Some(SkillQuery::technology(technology))}>
+                        {technology}
+                    </Link<Route, SkillQuery>>
+                }) }
+            </div>
+        };
+
         html! {
-            <div class="box jobbox">
+            <div class="box jobbox" ref={self.node.clone()}>
                 <div class="columns is-mobile is-centered">
                     <div class="column is-5">
-                        <div class="title is-5">{&self.job.title}</div>
-                        <div class="subtitle is-6">{&self.job.company}</div>
+                        <div class="title is-5">{&job.title}</div>
+                        <div class="subtitle is-6">{&job.company}</div>
+                        <div class="tags jobtags">
+                            <span class="tag is-black">{locale.t(&format!("job.{}", job.arrangement.key()))}</span>
+                            <span class="tag is-black">{locale.t(&format!("job.{}", job.employment.key()))}</span>
+                        </div>
                     </div>
                     <div class="column is-2 has-text-centered">
                         <div class="button is-white is-outlined jobbutton"
@@ -204,8 +233,9 @@ impl Component for JobCard {
                         </div>
                     </div>
                     <div class="column is-5 has-text-right">
-                        <div class="title is-5">{&self.job.start} {" - "} {&self.job.end}</div>
-                        <div class="subtitle is-6">{"Bucharest, Romania"}</div>
+                        <div class="title is-5">{job.start} {" - "} {end}</div>
+                        <div class="subtitle is-7 gray4 jobtenure">{tenure}</div>
+                        <div class="subtitle is-6">{&job.location}</div>
                     </div>
                 </div>
                 <div class={classes!("columns", "is-centered", "is-mobile", menu_class)}>
@@ -213,9 +243,9 @@ impl Component for JobCard {
                     {show_button!(Tech)}
                     {show_button!(Resp)}
                 </div>
-                {show_text!(Story, show_story(), story_div)}
-                {show_text!(Tech, show_tech(), tech_div)}
-                {show_text!(Resp, show_resp(), resp_div)}
+                {show_text!(Story, show_story(), job.story)}
+                {show_text!(Tech, show_tech(), job.tech, tech_chips)}
+                {show_text!(Resp, show_resp(), job.resp)}
             </div>
         }
     }
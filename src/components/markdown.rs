@@ -0,0 +1,141 @@
+use crate::components::sanitize::Sanitizer;
+use pulldown_cmark::{CodeBlockKind, Event, HeadingLevel, Parser, Tag};
+use yew::prelude::*;
+use yew::virtual_dom::VTag;
+
+/// Renders Markdown to a virtual DOM tree wrapped in a Bulma `content` div.
+/// Both the Markdown elements and any raw HTML go through the same [`Sanitizer`].
+pub fn render(markdown: &str) -> Html {
+    let mut root = VTag::new("div");
+    root.add_attribute("class", "content");
+    let mut out = Sanitizer::new(root);
+
+    for event in Parser::new(markdown) {
+        match event {
+            Event::Start(tag) => {
+                let (name, attrs) = element(&tag);
+                let attrs: Vec<(&str, &str)> =
+                    attrs.iter().map(|(k, v)| (*k, v.as_str())).collect();
+                out.open(name, &attrs);
+            }
+            Event::End(tag) => out.close(element(&tag).0),
+            Event::Text(text) => out.text(&text),
+            Event::Code(code) => {
+                out.open("code", &[]);
+                out.text(&code);
+                out.close("code");
+            }
+            Event::SoftBreak => out.text(" "),
+            Event::HardBreak => out.open("br", &[]),
+            Event::Rule => out.open("hr", &[]),
+            Event::Html(html) => out.html(&html),
+            Event::FootnoteReference(_) | Event::TaskListMarker(_) => {}
+        }
+    }
+
+    out.finish()
+}
+
+fn element(tag: &Tag) -> (&'static str, Vec<(&'static str, String)>) {
+    match tag {
+        Tag::Paragraph => ("p", vec![]),
+        Tag::Heading(level, _, _) => (
+            match level {
+                HeadingLevel::H1 => "h1",
+                HeadingLevel::H2 => "h2",
+                HeadingLevel::H3 => "h3",
+                HeadingLevel::H4 => "h4",
+                HeadingLevel::H5 => "h5",
+                HeadingLevel::H6 => "h6",
+            },
+            vec![],
+        ),
+        Tag::BlockQuote => ("blockquote", vec![]),
+        Tag::CodeBlock(CodeBlockKind::Fenced(lang)) if !lang.is_empty() => {
+            ("pre", vec![("class", format!("language-{}", lang))])
+        }
+        Tag::CodeBlock(_) => ("pre", vec![]),
+        Tag::List(Some(start)) if *start != 1 => ("ol", vec![("start", start.to_string())]),
+        Tag::List(Some(_)) => ("ol", vec![]),
+        Tag::List(None) => ("ul", vec![]),
+        Tag::Item => ("li", vec![]),
+        Tag::Emphasis => ("em", vec![]),
+        Tag::Strong => ("strong", vec![]),
+        Tag::Strikethrough => ("del", vec![]),
+        Tag::Link(_, href, title) if title.is_empty() => ("a", vec![("href", href.to_string())]),
+        Tag::Link(_, href, title) => (
+            "a",
+            vec![("href", href.to_string()), ("title", title.to_string())],
+        ),
+        // Images are rendered as their alt text, which arrives as Text events.
+        Tag::Image(..) => ("span", vec![]),
+        Tag::FootnoteDefinition(_)
+        | Tag::Table(_)
+        | Tag::TableHead
+        | Tag::TableRow
+        | Tag::TableCell => ("div", vec![]),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::sanitize::tests::markup;
+
+    fn html(markdown: &str) -> String {
+        let html = markup(&render(markdown));
+        let inner = html.strip_prefix("<div class=\"content\">").unwrap();
+        inner.strip_suffix("</div>").unwrap().to_string()
+    }
+
+    #[test]
+    fn renders_markdown_elements() {
+        assert_eq!(
+            html("# Title\n\nSome *em* and **strong** with `code`."),
+            "<h1>Title</h1><p>Some <em>em</em> and <strong>strong</strong> with <code>code</code>.</p>"
+        );
+        assert_eq!(
+            html("3. a\n4. b\n\n- c"),
+            "<ol start=\"3\"><li>a</li><li>b</li></ol><ul><li>c</li></ul>"
+        );
+        assert_eq!(
+            html("a  \nb\n\n---\n> q"),
+            "<p>a<br>b</p><hr><blockquote><p>q</p></blockquote>"
+        );
+        assert_eq!(
+            html("```rust\nfn x() {}\n```"),
+            "<pre class=\"language-rust\">fn x() {}\n</pre>"
+        );
+        assert_eq!(
+            html("![alt *text*](x.png)"),
+            "<p><span>alt <em>text</em></span></p>"
+        );
+    }
+
+    #[test]
+    fn links_go_through_the_url_checks() {
+        assert_eq!(
+            html("[x](javascript:alert(1)) [y](https://example.com \"t\") [z](/cv)"),
+            "<p><a>x</a> \
+             <a href=\"https://example.com\" rel=\"noopener noreferrer\" target=\"_blank\" title=\"t\">y</a> \
+             <a href=\"/cv\">z</a></p>"
+        );
+    }
+
+    #[test]
+    fn raw_html_is_sanitized() {
+        assert_eq!(html("a <script>alert(1)</script> b"), "<p>a  b</p>");
+        assert_eq!(
+            html("<script>\nalert(1)\n</script>\n\nafter"),
+            "\n<p>after</p>"
+        );
+        assert_eq!(
+            html("<img src=x onerror=alert(1)> <b onclick=x>b</b>"),
+            "<p><img src=\"x\"> <b>b</b></p>"
+        );
+        assert_eq!(
+            html("<iframe src=x></iframe>ok <a href=\"javascript:x\">l</a>"),
+            "ok <a>l</a>"
+        );
+    }
+}
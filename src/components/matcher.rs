@@ -0,0 +1,534 @@
+use crate::components::skills::SkillQuery;
+use crate::i18n::Locale;
+use crate::manifest::{Level, Skill};
+use crate::Route;
+use std::collections::BTreeMap;
+use std::rc::Rc;
+use web_sys::HtmlTextAreaElement;
+use yew::prelude::*;
+use yew_router::prelude::*;
+
+/// Other spellings of technologies, by the name they stand for.
+const ALIASES: &[(&str, &str)] = &[
+    ("k8s", "Kubernetes"),
+    ("kube", "Kubernetes"),
+    ("golang", "GoLang"),
+    ("Go", "GoLang"),
+    ("JS", "JavaScript"),
+    ("ECMAScript", "JavaScript"),
+    ("TS", "TypeScript"),
+    ("React", "JS/TS: React"),
+    ("ReactJS", "JS/TS: React"),
+    ("React.js", "JS/TS: React"),
+    ("Vue", "JS/TS: VueJS"),
+    ("Vue.js", "JS/TS: VueJS"),
+    ("Linux", "GNU/Linux"),
+    ("Red Hat", "RHEL"),
+    ("Amazon Web Services", "AWS"),
+    ("Google Cloud", "GCP"),
+    ("Microsoft Azure", "Azure"),
+    ("DigitalOcean", "Digital Ocean"),
+    ("GitHub Actions", "GitHub CI/CD"),
+    ("GitLab CI", "GitLab CI/CD"),
+    ("Python3", "Python"),
+    ("REST", "RESTful"),
+    ("SSH", "OpenSSH"),
+    ("TLS", "OpenSSL"),
+    ("SGX", "Intel SGX"),
+    ("SEV", "AMD SEV-SNP"),
+    ("Hyper-V", "HyperV"),
+    ("ESXi", "VMware ESXi"),
+    ("vSphere", "VMware vCenter"),
+    ("Node", "Node.js"),
+    ("NodeJS", "Node.js"),
+    ("Postgres", "PostgreSQL"),
+    ("Mongo", "MongoDB"),
+    ("WASM", "WebAssembly"),
+    (".NET", "C#"),
+    ("systemd-resolved", "resolved"),
+    ("Portage", "portage, emerge"),
+    ("APT", "dpkg, apt"),
+];
+
+/// Names that are also everyday English words. They only count when written as the name,
+/// e.g. "Rust" or "Spring", and not at all when that spelling is lowercase, as in "resolved";
+/// parts of compound names that are such words are left out.
+const COMMON_WORDS: &[&str] = &[
+    "apt",
+    "bootstrap",
+    "chef",
+    "consul",
+    "emerge",
+    "envoy",
+    "ethers",
+    "firewall",
+    "helm",
+    "kong",
+    "lambda",
+    "mac",
+    "namespaces",
+    "nomad",
+    "packer",
+    "portage",
+    "puppet",
+    "rails",
+    "resolved",
+    "ruby",
+    "rust",
+    "sentry",
+    "spark",
+    "spring",
+    "squid",
+    "substrate",
+    "swift",
+    "tailwind",
+    "tonic",
+    "vault",
+    "windows",
+    "yew",
+];
+
+/// Technologies that job postings commonly ask for, so that the ones without a skill
+/// can be reported as missing.
+const VOCABULARY: &[&str] = &[
+    "C#",
+    "Node.js",
+    "Deno",
+    "Ruby",
+    "Rails",
+    "Kotlin",
+    "Swift",
+    "Scala",
+    "Elixir",
+    "Erlang",
+    "Haskell",
+    "Perl",
+    "Zig",
+    "Angular",
+    "Svelte",
+    "Next.js",
+    "Flask",
+    "FastAPI",
+    "Spring",
+    "WebAssembly",
+    "Tokio",
+    "Axum",
+    "SQL",
+    "PostgreSQL",
+    "MySQL",
+    "MariaDB",
+    "SQLite",
+    "MongoDB",
+    "Redis",
+    "Cassandra",
+    "DynamoDB",
+    "Elasticsearch",
+    "Kafka",
+    "RabbitMQ",
+    "NATS",
+    "Prometheus",
+    "Grafana",
+    "Loki",
+    "Datadog",
+    "Splunk",
+    "Sentry",
+    "Zabbix",
+    "Nagios",
+    "Helm",
+    "Jenkins",
+    "ArgoCD",
+    "Pulumi",
+    "Puppet",
+    "Chef",
+    "SaltStack",
+    "Vagrant",
+    "Packer",
+    "Nomad",
+    "Consul",
+    "Vault",
+    "Envoy",
+    "Linkerd",
+    "Traefik",
+    "Caddy",
+    "Podman",
+    "containerd",
+    "OpenShift",
+    "Rancher",
+    "Cilium",
+    "Calico",
+    "eBPF",
+    "nftables",
+    "DPDK",
+    "Lambda",
+    "CloudFormation",
+    "Ethereum",
+    "Spark",
+    "Hadoop",
+    "Airflow",
+    "TensorFlow",
+    "PyTorch",
+    "CUDA",
+    "LLVM",
+    "OAuth",
+    "LDAP",
+    "Kerberos",
+    "Active Directory",
+    "Jira",
+];
+
+#[derive(Clone, PartialEq)]
+enum Target {
+    Skill(String),
+    Missing(String),
+}
+
+/// A skill asked for by the posting, with the words that asked for it.
+#[derive(PartialEq)]
+pub struct Matched {
+    pub technology: String,
+    pub level: Level,
+    pub mentions: Vec<String>,
+}
+
+#[derive(Default, PartialEq)]
+pub struct Report {
+    pub matched: Vec<Matched>,
+    pub missing: Vec<String>,
+}
+
+impl Report {
+    /// The share of the technologies in the posting that have a skill, in percent.
+    pub fn coverage(&self) -> Option<usize> {
+        let total = self.matched.len() + self.missing.len();
+        (total > 0).then(|| self.matched.len() * 100 / total)
+    }
+}
+
+fn is_common(term: &str) -> bool {
+    COMMON_WORDS.contains(&term.to_lowercase().as_str())
+}
+
+/// Terms of one or two letters and common words are matched case-sensitively.
+fn is_exact(term: &str) -> bool {
+    term.chars().count() <= 2 || is_common(term)
+}
+
+/// Parts of compound names such as "Rust: Actix", "QEMU/KVM" or "dpkg, apt", except for
+/// the ones too short or too common to stand for the skill, like "apt".
+fn parts(technology: &str) -> impl Iterator<Item = &str> {
+    technology
+        .split([',', ':', '/'])
+        .map(str::trim)
+        .filter(|p| !p.is_empty())
+        .filter(|p| !is_exact(p))
+}
+
+/// Every term to look for, longest first, with what it points to. Full skill names win
+/// over aliases, which win over parts of compound names, which win over the vocabulary.
+fn terms(skills: &[Rc<Skill>]) -> Vec<(String, Target)> {
+    let mut terms: Vec<(String, Target)> = Vec::new();
+    let mut add = |term: &str, target: Target| {
+        // A lowercase common word reads like prose, so only its aliases can find it.
+        let prose = is_common(term) && term.chars().all(|c| !c.is_uppercase());
+        if !prose && !terms.iter().any(|(t, _)| t.eq_ignore_ascii_case(term)) {
+            terms.push((term.to_string(), target));
+        }
+    };
+    let resolve = |name: &str| {
+        skills
+            .iter()
+            .find(|s| s.technology.eq_ignore_ascii_case(name))
+            .or_else(|| {
+                skills
+                    .iter()
+                    .find(|s| parts(&s.technology).any(|p| p.eq_ignore_ascii_case(name)))
+            })
+            .map_or(Target::Missing(name.to_string()), |s| {
+                Target::Skill(s.technology.clone())
+            })
+    };
+    for skill in skills {
+        add(&skill.technology, Target::Skill(skill.technology.clone()));
+    }
+    for (alias, name) in ALIASES {
+        add(alias, resolve(name));
+    }
+    for skill in skills {
+        for part in parts(&skill.technology) {
+            add(part, resolve(part));
+        }
+    }
+    for name in VOCABULARY {
+        add(name, resolve(name));
+    }
+    terms.sort_by_key(|(term, _)| std::cmp::Reverse(term.chars().count()));
+    terms
+}
+
+/// Finds `term` as a whole word: "C" matches neither "C++" nor "CSS", but matches "C,".
+/// Short and common terms are case-sensitive, so that "go" is not "Go" and "MAC" is not "Mac".
+fn find_word(text: &[char], taken: &[bool], term: &str) -> Vec<usize> {
+    let exact = is_exact(term);
+    let term: Vec<char> = term.chars().collect();
+    let same = |a: &char, b: &char| match exact {
+        true => a == b,
+        false => a.to_lowercase().eq(b.to_lowercase()),
+    };
+    let word = |c: Option<&char>| c.is_some_and(|c| c.is_alphanumeric() || *c == '+' || *c == '#');
+    let mut found = Vec::new();
+    for start in 0..(text.len() + 1).saturating_sub(term.len()) {
+        let end = start + term.len();
+        let joined =
+            text.get(end) == Some(&'.') && text.get(end + 1).is_some_and(|c| c.is_alphanumeric());
+        let free = taken[start..end].iter().all(|t| !t);
+        let alone = start == 0 || !text[start - 1].is_alphanumeric();
+        if text[start..end].iter().zip(&term).all(|(a, b)| same(a, b))
+            && free
+            && alone
+            && !word(text.get(end))
+            && !joined
+        {
+            found.push(start);
+        }
+    }
+    found
+}
+
+/// Matches the technologies mentioned in a job posting against the skills; a technology
+/// with several skills (in different categories) counts once, at its highest level.
+pub fn analyze(posting: &str, skills: &[Rc<Skill>]) -> Report {
+    let text: Vec<char> = posting.chars().collect();
+    let mut taken = vec![false; text.len()];
+    let mut matched: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    let mut missing: Vec<String> = Vec::new();
+    // Longer terms claim their text first, so "Kubernetes CNI" is not also "Kubernetes".
+    for (term, target) in terms(skills) {
+        let len = term.chars().count();
+        for start in find_word(&text, &taken, &term) {
+            taken[start..start + len].iter_mut().for_each(|t| *t = true);
+            let mention: String = text[start..start + len].iter().collect();
+            match &target {
+                Target::Skill(technology) => {
+                    let mentions = matched.entry(technology.clone()).or_default();
+                    if !mentions.contains(&mention) {
+                        mentions.push(mention);
+                    }
+                }
+                Target::Missing(name) => {
+                    if !missing.contains(name) {
+                        missing.push(name.clone());
+                    }
+                }
+            }
+        }
+    }
+    let mut matched: Vec<Matched> = matched
+        .into_iter()
+        .filter_map(|(technology, mentions)| {
+            let level = skills
+                .iter()
+                .filter(|s| s.technology == technology)
+                .map(|s| s.level.clone())
+                .max()?;
+            Some(Matched {
+                technology,
+                level,
+                mentions,
+            })
+        })
+        .collect();
+    matched.sort_by(|a, b| b.level.cmp(&a.level).then(a.technology.cmp(&b.technology)));
+    missing.sort();
+    Report { matched, missing }
+}
+
+#[derive(PartialEq, Properties)]
+pub struct Props {
+    pub skills: Rc<Vec<Rc<Skill>>>,
+    pub locale: Locale,
+}
+
+/// Paste a job posting, see which of its technologies the skills cover.
+/// Everything runs in the browser; the posting is never sent anywhere.
+pub struct PostingMatcher {
+    input: NodeRef,
+    report: Report,
+}
+
+impl Component for PostingMatcher {
+    type Message = ();
+    type Properties = Props;
+
+    fn create(_ctx: &Context<Self>) -> Self {
+        Self {
+            input: NodeRef::default(),
+            report: Report::default(),
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, _msg: Self::Message) -> bool {
+        let posting = self
+            .input
+            .cast::<HtmlTextAreaElement>()
+            .map(|i| i.value())
+            .unwrap_or_default();
+        let report = analyze(&posting, &ctx.props().skills);
+        if report == self.report {
+            return false;
+        }
+        self.report = report;
+        true
+    }
+
+    fn changed(&mut self, ctx: &Context<Self>) -> bool {
+        self.update(ctx, ())
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let locale = ctx.props().locale;
+        let skills_route = Route::Skills.localized(locale);
+        html! {
+            <div class="matcher">
+                <textarea class="textarea is-small" rows="8" ref={self.input.clone()}
+                    placeholder={locale.t("skills.posting")}
+                    oninput={ctx.link().callback(|_| ())}/>
+                if let Some(coverage) = self.report.coverage() {
+                    <div class="title is-5 has-text-centered matcher-coverage">
+                        {format!("{}% {}", coverage, locale.t("skills.coverage"))}
+                        <div class="subtitle is-6 gray4">
+                            {format!("{} / {}", self.report.matched.len(),
+                                self.report.matched.len() + self.report.missing.len())}
+                        </div>
+                    </div>
+                    <div class="columns">
+                        <div class="column">
+                            <div class="has-text-weight-bold">{locale.t("skills.matched")}</div>
+                            { for self.report.matched.iter().map(|m| html! {
+                                <div class="columns is-mobile is-gapless is-marginless">
+                                    <div class="column is-5">
+                                        <Link<Route, SkillQuery> to={skills_route.clone()}
+                                            query={Some(SkillQuery::technology(&m.technology))}>
+                                            {&m.technology}
+                                        </Link<Route, SkillQuery>>
+                                    </div>
+                                    <div class="column is-3 skill-level">{m.level.clone()}</div>
+                                    <div class="column is-4 gray4 has-text-right">{m.mentions.join(", ")}</div>
+                                </div>
+                            }) }
+                        </div>
+                        <div class="column is-4">
+                            <div class="has-text-weight-bold">{locale.t("skills.missing")}</div>
+                            <div class="tags">
+                                { for self.report.missing.iter().map(|m| html! {
+                                    <span class="tag is-black">{m}</span>
+                                }) }
+                            </div>
+                        </div>
+                    </div>
+                } else {
+                    <div class="subtitle is-6 has-text-centered gray4">{locale.t("skills.posting_hint")}</div>
+                }
+            </div>
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn skills(skills: &[(&str, usize)]) -> Vec<Rc<Skill>> {
+        skills
+            .iter()
+            .map(|(technology, level)| {
+                Rc::new(Skill {
+                    categories: vec!["Test".to_string()],
+                    technology: technology.to_string(),
+                    level: Level(*level),
+                    note: String::new(),
+                    years: None,
+                    last_used: None,
+                    trend: None,
+                    translations: Default::default(),
+                })
+            })
+            .collect()
+    }
+
+    fn matched(report: &Report) -> Vec<&str> {
+        report
+            .matched
+            .iter()
+            .map(|m| m.technology.as_str())
+            .collect()
+    }
+
+    const SKILLS: &[(&str, usize)] = &[
+        ("Rust", 5),
+        ("Rust: Actix", 4),
+        ("resolved", 3),
+        ("portage, emerge", 3),
+        ("dpkg, apt", 4),
+        ("firewall, iptables", 4),
+        ("Mac", 2),
+        ("GoLang", 3),
+        ("Kubernetes", 4),
+        ("Kubernetes CNI", 3),
+    ];
+
+    #[test]
+    fn ignores_names_used_as_words() {
+        let report = analyze(
+            "Incidents are resolved quickly, new ideas emerge daily, an apt candidate \
+             knows MAC addresses, keeps the firewall tidy and does not let skills rust.",
+            &skills(SKILLS),
+        );
+        assert_eq!(matched(&report), Vec::<&str>::new());
+        assert!(report.missing.is_empty());
+    }
+
+    #[test]
+    fn finds_names_as_written() {
+        let report = analyze(
+            "Rust, Actix, systemd-resolved, Portage, APT, iptables, a Mac and Go.",
+            &skills(SKILLS),
+        );
+        assert_eq!(
+            matched(&report),
+            vec![
+                "Rust",
+                "Rust: Actix",
+                "dpkg, apt",
+                "firewall, iptables",
+                "GoLang",
+                "portage, emerge",
+                "resolved",
+                "Mac",
+            ]
+        );
+        let resolved = report.matched.iter().find(|m| m.technology == "resolved");
+        assert_eq!(resolved.unwrap().mentions, vec!["systemd-resolved"]);
+    }
+
+    #[test]
+    fn longer_names_claim_their_text_first() {
+        let report = analyze("We run Kubernetes CNI plugins.", &skills(SKILLS));
+        assert_eq!(matched(&report), vec!["Kubernetes CNI"]);
+        let report = analyze("Kubernetes, k8s and kube", &skills(SKILLS));
+        assert_eq!(
+            report.matched[0].mentions,
+            vec!["Kubernetes", "kube", "k8s"]
+        );
+    }
+
+    #[test]
+    fn reports_the_vocabulary_without_a_skill() {
+        let report = analyze(
+            "Kafka and Spring Boot; in spring we spark joy.",
+            &skills(SKILLS),
+        );
+        assert!(report.matched.is_empty());
+        assert_eq!(report.missing, vec!["Kafka", "Spring"]);
+        assert_eq!(report.coverage(), Some(0));
+    }
+}
@@ -1,3 +1,16 @@
+pub mod charts;
+pub mod contact_form;
+pub mod content;
+pub mod export;
+pub mod fuzzy;
+pub mod gallery;
 pub mod job;
+pub mod markdown;
+pub mod matcher;
+pub mod picture;
 pub mod projects;
+pub mod query;
+pub mod sanitize;
 pub mod skills;
+pub mod timeline;
+pub mod transition;
@@ -0,0 +1,87 @@
+use crate::manifest;
+use yew::prelude::*;
+
+#[derive(PartialEq, Properties)]
+pub struct Props {
+    /// A manifest image: a remote URL or a file in `assets/images/`.
+    pub src: String,
+    pub alt: String,
+    #[prop_or_default]
+    pub class: Classes,
+    /// How wide the image is drawn, so the browser can pick a variant.
+    #[prop_or_else(|| "100vw".to_string())]
+    pub sizes: String,
+    /// Images above the fold should not wait for the page to be laid out.
+    #[prop_or_default]
+    pub eager: bool,
+}
+
+pub enum Msg {
+    Failed,
+}
+
+/// An image with the responsive variants made by `examples/build_images.rs`, falling back to
+/// a placeholder with its alternative text when it cannot be loaded.
+pub struct Picture {
+    failed: bool,
+}
+
+impl Component for Picture {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(_ctx: &Context<Self>) -> Self {
+        Self { failed: false }
+    }
+
+    fn update(&mut self, _ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::Failed => self.failed = true,
+        }
+        true
+    }
+
+    fn changed(&mut self, _ctx: &Context<Self>) -> bool {
+        self.failed = false;
+        true
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let props = ctx.props();
+        let set = manifest::image_set(&props.src);
+        if self.failed {
+            let style = set
+                .map(|s| format!("aspect-ratio: {} / {}", s.width, s.height))
+                .unwrap_or_default();
+            return html! {
+                <div class={classes!("picture-failed", props.class.clone())} role="img"
+                    aria-label={props.alt.clone()} {style}>
+                    {&props.alt}
+                </div>
+            };
+        }
+        let loading = match props.eager {
+            true => "eager",
+            false => "lazy",
+        };
+        let onerror = ctx.link().callback(|_| Msg::Failed);
+        match set {
+            Some(set) => html! {
+                <picture>
+                    if !set.avif.is_empty() {
+                        <source type="image/avif" srcset={set.avif.clone()} sizes={props.sizes.clone()}/>
+                    }
+                    <source type="image/webp" srcset={set.webp.clone()} sizes={props.sizes.clone()}/>
+                    <img class={props.class.clone()} src={set.fallback.clone()} alt={props.alt.clone()}
+                        width={set.width.to_string()} height={set.height.to_string()}
+                        style={format!("background-color: {}", set.color)}
+                        {loading} decoding="async" {onerror}/>
+                </picture>
+            },
+            None => html! {
+                <img class={props.class.clone()} src={props.src.clone()} alt={props.alt.clone()}
+                    {loading} decoding="async" {onerror}/>
+            },
+        }
+    }
+}
@@ -1,143 +1,164 @@
+use crate::components::picture::Picture;
+use crate::components::skills::SkillQuery;
+use crate::i18n::Locale;
+use crate::manifest::{self, ProjectMeta, YearMonth};
+use crate::Route;
+use std::rc::Rc;
 use yew::prelude::*;
+use yew_router::prelude::*;
 
-#[derive(PartialEq)]
-pub enum Project {
-    DeTEE,
-    GheoTech,
-    MerkleTree,
-    RustPhantom,
-    ActixServer,
+pub struct ProjectCard;
+
+#[derive(PartialEq, Properties)]
+pub struct Props {
+    pub project: Rc<ProjectMeta>,
+    pub locale: Locale,
+}
+
+/// The license of the manifest, or else the one recorded for the repository in the committed
+/// `repo-stats.json` snapshot.
+pub fn license(project: &ProjectMeta) -> Option<String> {
+    project
+        .license
+        .clone()
+        .or_else(|| manifest::repo_stats(&project.id).and_then(|s| s.license))
 }
 
-pub struct ProjectMeta {
-    title: String,
-    description: String,
-    demo: Option<String>,
-    source: String,
-    img: String,
+/// Year, status and license, as one line of text. The license detected in the repository
+/// is used when the manifest has none.
+pub fn facts(project: &ProjectMeta, locale: Locale) -> String {
+    let mut facts: Vec<String> = Vec::new();
+    if let Some(year) = project.year {
+        facts.push(year.to_string());
+    }
+    if let Some(status) = project.status {
+        facts.push(locale.t(&format!("projects.{}", status.key())));
+    }
+    if let Some(license) = license(project) {
+        facts.push(format!("{} {}", license, locale.t("projects.license")));
+    }
+    facts.join(" · ")
 }
 
-impl From<&Project> for ProjectMeta {
-    fn from(p: &Project) -> Self {
-        macro_rules! match_p {
-            ( $( $project_key:ident => {
-                    $title:expr, $desc:expr, $demo: expr, $source: expr, $img:  expr
-            }, )+
-            ) => { match p {
-                $( Project::$project_key => ProjectMeta {
-                    title: $title.to_string(),
-                    description: $desc.to_string(),
-                    demo: $demo,
-                    source: $source.to_string(),
-                    img: $img.to_string(),
-                }, )+
-            }  };
+/// Stars, main languages and the time since the last commit, from the repository snapshot.
+pub fn repo_badges(project: &ProjectMeta, locale: Locale) -> Html {
+    let Some(stats) = manifest::repo_stats(&project.id) else {
+        return html! {};
+    };
+    let updated = stats.last_commit_month().map(|month| {
+        match month.months_until(YearMonth::today()).saturating_sub(1) {
+            0 => locale.t("projects.updated_recently"),
+            months => format!(
+                "{} {} {}",
+                locale.t("projects.updated"),
+                locale.duration(months),
+                locale.t("projects.ago")
+            )
+            .trim()
+            .to_string(),
         }
-        match_p!(
-            DeTEE => {
-                "DeTEE",
-                "A community powered cloud platform based on Trusted Execution Environments (TEEs).
-                    This is probably the biggest project of my entire career.",
-                Some("https://detee.ltd".to_string()),
-                "https://gitea.detee.cloud/general",
-                "https://filedn.com/lRvVNpEzu7mVLW5g3Ak9iOk/detee.png"
-            },
-            GheoTech => {
-                "This Website",
-                "Normally developers will create their portofolio website using React.
-                    In my case, however, I thought that a Rust dev should use a Rust framework.
-                    Because of this, the tech stack used is Yew (Rust) + Bulma (html/css).
-                    Feel free to fork!",
-                None,
-                "https://github.com/Gheo-Tech/yew-portfolio",
-                "https://filedn.com/lRvVNpEzu7mVLW5g3Ak9iOk/gheotech.png"
-            },
-            MerkleTree => {
-                "Merkle Tree Demo",
-                "Graphical representation of a Merkle Tree, created with Yew.
-                    Helps at gaining a better understand how a blockchain works.
-                    Allows you to add transactions and also to highlight the
-                    hashes required to validate a transaction.",
-                Some("https://gheo-tech.github.io/yew-merkle-tree".to_string()),
-                "https://github.com/Gheo-Tech/yew-merkle-tree",
-                "https://filedn.com/lRvVNpEzu7mVLW5g3Ak9iOk/merkeltree.png"
-            },
-            RustPhantom => {
-                "Rust - Phantom POC",
-                "Small POC showcasing how Seed (a Rust front-end framework) can be used
-                    to interact with the Phantom Wallet (for the Solana chain). This technology
-                    can be used as an alternative to TypeScript/React to create dApps.",
-                Some("https://gheo.tech/rust-phantom-poc/".to_string()),
-                "https://github.com/Gheo-Tech/rust-phantom-poc",
-                "https://filedn.com/lRvVNpEzu7mVLW5g3Ak9iOk/seedphantom.png"
-            },
-            ActixServer => {
-                "Actix API for MongoDB",
-                "Actix WebServer simulating the blockchain part of a DeFi game. It was written
-                    via Test Driven Development and features Table Driven Testing based on
-                    declarative macros. I love the result so I am showcasing it whenever
-                    I have the chance.",
-                None,
-                "https://github.com/Gheo-Tech/sacred-queens/tree/main/demo/server/",
-                "https://filedn.com/lRvVNpEzu7mVLW5g3Ak9iOk/sacred_queens_demo_server.png"
-            },
-        )
+    });
+    html! {
+        <div class="tags is-centered repobadges">
+            <span class="tag is-black" title={locale.t("projects.stars")}>
+                {format!("★ {}", stats.stars)}
+            </span>
+            { for stats.languages.iter().take(3).map(|language| html! {
+                <span class="tag is-black">{language}</span>
+            }) }
+            if let Some(updated) = updated {
+                <span class="tag is-black gray4" title={stats.last_commit.clone()}>{updated}</span>
+            }
+        </div>
     }
 }
 
-pub struct ProjectCard {
-    project: ProjectMeta,
+/// The technologies of the project, each linking to its skills.
+pub fn tech_chips(project: &ProjectMeta, locale: Locale) -> Html {
+    html! {
+        <div class="tags is-centered techchips">
+            { for project.skills.iter().map(|technology| html! {
+                <Link<Route, SkillQuery> classes={classes!("tag", "is-black")}
+                    to={Route::Skills.localized(locale)}
+                    query={Some(SkillQuery::technology(technology))}>
+                    {technology}
+                </Link<Route, SkillQuery>>
+            }) }
+        </div>
+    }
 }
 
-#[derive(PartialEq, Properties)]
-pub struct Props {
-    pub project: Project,
+pub fn demo_button(project: &ProjectMeta, locale: Locale) -> Html {
+    match &project.demo {
+        Some(s) => html! {
+            <a class="button is-white is-small is-outlined" target="_blank" href={s.clone()}>
+                {locale.t("projects.play")}
+            </a>
+        },
+        None => html! {},
+    }
+}
+
+pub fn source_button(project: &ProjectMeta, locale: Locale) -> Html {
+    html! {
+        <a class="button is-white is-small is-outlined" target="_blank" href={project.source.clone()}>
+            {locale.t("projects.code")}
+        </a>
+    }
 }
 
 impl Component for ProjectCard {
     type Message = ();
     type Properties = Props;
 
-    fn create(ctx: &Context<Self>) -> Self {
-        Self {
-            project: ProjectMeta::from(&ctx.props().project),
-        }
+    fn create(_ctx: &Context<Self>) -> Self {
+        Self
     }
 
-    fn view(&self, _ctx: &Context<Self>) -> Html {
-        let play_button = match &self.project.demo {
-            Some(s) => html! {
-                <a target="_blank" href={format!("{}", s)}>
-                    <button class="button is-white is-small is-outlined">
-                        {"▶ play"}</button>
-                </a>
-            },
-            None => html! {},
-        };
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let project = &ctx.props().project;
+        let locale = ctx.props().locale;
+        let facts = facts(project, locale);
+        let detail = Route::ProjectDetail {
+            id: project.id.clone(),
+        }
+        .localized(locale);
         html! {
-            <button class="box has-text-centered projectbox">
+            <div class="box has-text-centered projectbox">
                 <div class="columns">
                     <div class="column">
-                        <div class="subtitle is-5">{&self.project.title}</div>
-                        <p class="subtitle is-italic is-6">{&self.project.description}</p>
+                        <div class="subtitle is-5">
+                            <Link<Route> to={detail.clone()}>{&project.title}</Link<Route>>
+                        </div>
+                        <p class="subtitle is-italic is-6">{&project.description}</p>
+                        if !facts.is_empty() {
+                            <p class="gray4 projectfacts">{facts}</p>
+                        }
+                        {tech_chips(project, locale)}
+                        {repo_badges(project, locale)}
                         <div class="columns has-text-centered is-mobile">
                             <div class="column">
-                                { play_button }
+                                {demo_button(project, locale)}
+                            </div>
+                            <div class="column">
+                                {source_button(project, locale)}
                             </div>
                             <div class="column">
-                                <a target="_blank" href={format!("{}", &self.project.source)}>
-                                    <button class="button is-white is-small is-outlined">
-                                        {"≺⁄≻ code"}
-                                    </button>
-                                </a>
+                                <Link<Route> classes={classes!("button", "is-white", "is-small", "is-outlined")}
+                                    to={detail.clone()}>
+                                    {locale.t("projects.details")}
+                                </Link<Route>>
                             </div>
                         </div>
                     </div>
                     <div class="column">
-                        <img class="projectimg" src={format!("{}", &self.project.img)}/>
+                        <Link<Route> to={detail}>
+                            <Picture class="projectimg" src={project.img.clone()} alt={project.title.clone()}
+                                sizes="(min-width: 769px) 460px, 100vw"/>
+                        </Link<Route>>
                     </div>
                 </div>
-            </button>
+            </div>
         }
     }
 }
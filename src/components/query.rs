@@ -0,0 +1,511 @@
+use crate::components::fuzzy::{self, Match, Ranked};
+use crate::manifest::{Skill, Trend};
+
+/// A parsed skills search such as `level>=4 cat:networking -aws "home lab" tech:rust*`,
+/// `years>5 used<2020` or `trend:fading`.
+/// Every term has to match; plain words are matched fuzzily against all columns, but
+/// excluded words (`-aws`) only as they are, so that a typo match does not hide a skill.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Query {
+    terms: Vec<Term>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Term {
+    negated: bool,
+    kind: Kind,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Kind {
+    Text(Option<Field>, Pattern),
+    Number(Number, Cmp, usize),
+    Trend(Trend),
+}
+
+/// The numeric attributes of a skill; skills without the attribute match no comparison.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Number {
+    Level,
+    Years,
+    Used,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Pattern {
+    Fuzzy(String),
+    Phrase(String),
+    Glob(Vec<char>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Cmp {
+    Lt,
+    Le,
+    Eq,
+    Ge,
+    Gt,
+}
+
+/// The searchable columns, in the order of `Ranked::positions`, with their weights.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Field {
+    Tech,
+    Cat,
+    Note,
+}
+
+const FIELDS: [(Field, u32); 3] = [(Field::Tech, 3), (Field::Cat, 2), (Field::Note, 1)];
+
+const NUMBERS: [(&str, Number); 3] = [
+    ("level", Number::Level),
+    ("years", Number::Years),
+    ("used", Number::Used),
+];
+
+const OPERATORS: [(&str, Cmp); 5] = [
+    (">=", Cmp::Ge),
+    ("<=", Cmp::Le),
+    (">", Cmp::Gt),
+    ("<", Cmp::Lt),
+    ("=", Cmp::Eq),
+];
+
+/// A search that could not be parsed: `key` names the message in the locale bundles
+/// and `token` is the offending part of the input.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError {
+    pub key: &'static str,
+    pub token: String,
+}
+
+impl Field {
+    fn parse(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "tech" | "technology" => Some(Field::Tech),
+            "cat" | "category" => Some(Field::Cat),
+            "note" => Some(Field::Note),
+            _ => None,
+        }
+    }
+}
+
+impl Number {
+    fn of(self, skill: &Skill) -> Option<usize> {
+        match self {
+            Number::Level => Some(skill.level.0),
+            Number::Years => skill.years.map(usize::from),
+            Number::Used => skill.last_used.map(usize::from),
+        }
+    }
+
+    /// The accepted values and the message key for anything else.
+    fn range(self) -> (std::ops::RangeInclusive<usize>, &'static str) {
+        match self {
+            Number::Level => (1..=5, "skills.error_level"),
+            Number::Years => (0..=99, "skills.error_years"),
+            Number::Used => (1900..=2100, "skills.error_used"),
+        }
+    }
+}
+
+impl Cmp {
+    fn holds(self, level: usize, value: usize) -> bool {
+        match self {
+            Cmp::Lt => level < value,
+            Cmp::Le => level <= value,
+            Cmp::Eq => level == value,
+            Cmp::Ge => level >= value,
+            Cmp::Gt => level > value,
+        }
+    }
+}
+
+impl Query {
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        let terms = tokens(input)
+            .iter()
+            .map(|t| Term::parse(t))
+            .collect::<Result<_, _>>()?;
+        Ok(Query { terms })
+    }
+
+    /// Rewrites a search so that it only matches `level`, keeping the other terms.
+    pub fn with_level(input: &str, level: usize) -> String {
+        let mut kept: Vec<String> = tokens(input)
+            .into_iter()
+            .filter(|t| {
+                !matches!(
+                    Term::parse(t),
+                    Ok(Term {
+                        negated: false,
+                        kind: Kind::Number(Number::Level, ..),
+                    })
+                )
+            })
+            .collect();
+        kept.push(format!("level={}", level));
+        kept.join(" ")
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.terms.is_empty()
+    }
+
+    /// Matches the skill against every term, with the positions to highlight in the
+    /// technology, the categories and the note. An empty query matches with score 0.
+    pub fn rank(&self, skill: &Skill) -> Option<Ranked> {
+        let categories = skill.categories.join(", ");
+        let texts = [skill.technology.as_str(), &categories, &skill.note];
+        let mut ranked = Ranked {
+            score: 0,
+            positions: vec![Vec::new(); FIELDS.len()],
+        };
+        for term in &self.terms {
+            match (term.find(skill, &texts), term.negated) {
+                (Some(_), true) | (None, false) => return None,
+                (Some((field, m)), false) => {
+                    ranked.score += m.score;
+                    ranked.positions[field].extend(m.positions);
+                }
+                (None, true) => {}
+            }
+        }
+        for positions in &mut ranked.positions {
+            positions.sort_unstable();
+            positions.dedup();
+        }
+        Some(ranked)
+    }
+}
+
+/// Splits the input on whitespace outside of double quotes.
+fn tokens(input: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut chars = input.chars().peekable();
+    loop {
+        while chars.next_if(|c| c.is_whitespace()).is_some() {}
+        if chars.peek().is_none() {
+            return tokens;
+        }
+        let mut token = String::new();
+        let mut quoted = false;
+        while let Some(c) = chars.next_if(|c| quoted || !c.is_whitespace()) {
+            quoted ^= c == '"';
+            token.push(c);
+        }
+        tokens.push(token);
+    }
+}
+
+impl Term {
+    fn parse(token: &str) -> Result<Self, ParseError> {
+        let error = |key| ParseError {
+            key,
+            token: token.to_string(),
+        };
+        let (negated, rest) = match token.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, token),
+        };
+        if rest.is_empty() {
+            return Err(error("skills.error_negation"));
+        }
+        // "level" on its own, or "levels", is an ordinary word.
+        let number = NUMBERS.iter().find_map(|(name, number)| {
+            rest.get(..name.len())
+                .filter(|n| n.eq_ignore_ascii_case(name))
+                .map(|_| (*number, &rest[name.len()..]))
+                .filter(|(_, op)| op.starts_with([':', '=', '<', '>']))
+        });
+        if let Some((number, op)) = number {
+            let op = op.strip_prefix(':').unwrap_or(op);
+            let (cmp, value) = OPERATORS
+                .iter()
+                .find_map(|(s, cmp)| op.strip_prefix(s).map(|v| (*cmp, v)))
+                .unwrap_or((Cmp::Eq, op));
+            let (range, key) = number.range();
+            let value = value
+                .parse()
+                .ok()
+                .filter(|v| range.contains(v))
+                .ok_or_else(|| error(key))?;
+            return Ok(Term {
+                negated,
+                kind: Kind::Number(number, cmp, value),
+            });
+        }
+        if let Some(value) = rest
+            .split_once(':')
+            .filter(|(name, _)| name.eq_ignore_ascii_case("trend"))
+            .map(|(_, value)| value)
+        {
+            let trend = Trend::ALL
+                .iter()
+                .find(|t| t.key().eq_ignore_ascii_case(value))
+                .ok_or_else(|| error("skills.error_trend"))?;
+            return Ok(Term {
+                negated,
+                kind: Kind::Trend(*trend),
+            });
+        }
+        // Unknown qualifiers such as "c++:" are searched for as they are.
+        let (field, value) = match rest.split_once(':') {
+            Some((name, value)) => match Field::parse(name) {
+                Some(field) => (Some(field), value),
+                None => (None, rest),
+            },
+            None => (None, rest),
+        };
+        let pattern = match value.strip_prefix('"') {
+            Some(quoted) => match quoted.strip_suffix('"') {
+                Some(phrase) if !phrase.contains('"') => Pattern::Phrase(phrase.to_string()),
+                _ => return Err(error("skills.error_quote")),
+            },
+            None if value.contains('*') => Pattern::Glob(fuzzy::lower(value)),
+            None if negated => Pattern::Phrase(value.to_string()),
+            None => Pattern::Fuzzy(value.to_string()),
+        };
+        match &pattern {
+            Pattern::Fuzzy(s) | Pattern::Phrase(s) if s.trim().is_empty() => {
+                Err(error("skills.error_empty"))
+            }
+            _ => Ok(Term {
+                negated,
+                kind: Kind::Text(field, pattern),
+            }),
+        }
+    }
+
+    /// A match on an attribute that is not highlighted and does not change the ranking.
+    fn attribute() -> (usize, Match) {
+        let m = Match {
+            score: 0,
+            positions: Vec::new(),
+        };
+        (0, m)
+    }
+
+    /// The best matching column and match, weighted by the column.
+    fn find(&self, skill: &Skill, texts: &[&str; 3]) -> Option<(usize, Match)> {
+        let (field, pattern) = match &self.kind {
+            Kind::Number(number, cmp, value) => {
+                let holds = number.of(skill).is_some_and(|n| cmp.holds(n, *value));
+                return holds.then(Term::attribute);
+            }
+            Kind::Trend(trend) => return (skill.trend == Some(*trend)).then(Term::attribute),
+            Kind::Text(field, pattern) => (field, pattern),
+        };
+        FIELDS
+            .iter()
+            .enumerate()
+            .filter(|(_, (f, _))| field.is_none_or(|field| field == *f))
+            .filter_map(|(i, (_, weight))| {
+                pattern.find(texts[i]).map(|m| {
+                    let m = Match {
+                        score: m.score * weight,
+                        positions: m.positions,
+                    };
+                    (i, m)
+                })
+            })
+            .max_by_key(|(_, m)| m.score)
+    }
+}
+
+impl Pattern {
+    fn find(&self, text: &str) -> Option<Match> {
+        match self {
+            Pattern::Fuzzy(word) => fuzzy::find(word, text),
+            Pattern::Phrase(phrase) => fuzzy::exact(phrase, text),
+            // Wildcards match whole items of comma-separated lists such as the categories.
+            Pattern::Glob(pattern) => {
+                let mut start = 0;
+                for item in text.split(", ") {
+                    let chars = fuzzy::lower(item);
+                    if glob(pattern, &chars) {
+                        return Some(Match {
+                            score: 100,
+                            positions: (start..start + chars.len()).collect(),
+                        });
+                    }
+                    start += chars.len() + 2;
+                }
+                None
+            }
+        }
+    }
+}
+
+fn glob(pattern: &[char], text: &[char]) -> bool {
+    match pattern.split_first() {
+        None => text.is_empty(),
+        Some(('*', rest)) => (0..=text.len()).any(|i| glob(rest, &text[i..])),
+        Some((c, rest)) => text.first() == Some(c) && glob(rest, &text[1..]),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::manifest::Level;
+
+    fn term(token: &str) -> Term {
+        Term::parse(token).unwrap()
+    }
+
+    fn text(field: Option<Field>, pattern: Pattern) -> Term {
+        Term {
+            negated: false,
+            kind: Kind::Text(field, pattern),
+        }
+    }
+
+    fn error(input: &str) -> &'static str {
+        Query::parse(input).unwrap_err().key
+    }
+
+    fn skill(technology: &str, category: &str, level: usize, note: &str) -> Skill {
+        Skill {
+            categories: vec![category.to_string()],
+            technology: technology.to_string(),
+            level: Level(level),
+            note: note.to_string(),
+            years: Some(3),
+            last_used: Some(2019),
+            trend: Some(Trend::Fading),
+            translations: Default::default(),
+        }
+    }
+
+    fn matches(input: &str, skill: &Skill) -> bool {
+        Query::parse(input).unwrap().rank(skill).is_some()
+    }
+
+    #[test]
+    fn splits_on_whitespace_outside_quotes() {
+        assert_eq!(
+            tokens("  rust \"home lab\"\tnote:\"a b\" "),
+            ["rust", "\"home lab\"", "note:\"a b\""]
+        );
+        assert!(Query::parse("   ").unwrap().is_empty());
+    }
+
+    #[test]
+    fn parses_qualifiers() {
+        let fuzzy = |s: &str| Pattern::Fuzzy(s.to_string());
+        assert_eq!(term("tech:rust"), text(Some(Field::Tech), fuzzy("rust")));
+        assert_eq!(
+            term("Technology:rust"),
+            text(Some(Field::Tech), fuzzy("rust"))
+        );
+        assert_eq!(term("cat:net"), text(Some(Field::Cat), fuzzy("net")));
+        assert_eq!(term("note:lab"), text(Some(Field::Note), fuzzy("lab")));
+        // Not a qualifier, so the whole token is searched for.
+        assert_eq!(term("c++:17"), text(None, fuzzy("c++:17")));
+        assert_eq!(term("trend:Fading").kind, Kind::Trend(Trend::Fading));
+    }
+
+    #[test]
+    fn parses_comparisons() {
+        let number = |token: &str| match term(token).kind {
+            Kind::Number(number, cmp, value) => (number, cmp, value),
+            kind => panic!("{:?}", kind),
+        };
+        assert_eq!(number("level>=4"), (Number::Level, Cmp::Ge, 4));
+        assert_eq!(number("level<=2"), (Number::Level, Cmp::Le, 2));
+        assert_eq!(number("LEVEL:3"), (Number::Level, Cmp::Eq, 3));
+        assert_eq!(number("level:>3"), (Number::Level, Cmp::Gt, 3));
+        assert_eq!(number("level=5"), (Number::Level, Cmp::Eq, 5));
+        assert_eq!(number("years>5"), (Number::Years, Cmp::Gt, 5));
+        assert_eq!(number("used<2020"), (Number::Used, Cmp::Lt, 2020));
+        // Without an operator these are plain words.
+        assert_eq!(
+            term("level"),
+            text(None, Pattern::Fuzzy("level".to_string()))
+        );
+        assert_eq!(
+            term("levels"),
+            text(None, Pattern::Fuzzy("levels".to_string()))
+        );
+    }
+
+    #[test]
+    fn parses_phrases_and_globs() {
+        assert_eq!(
+            term("\"home lab\""),
+            text(None, Pattern::Phrase("home lab".to_string()))
+        );
+        assert_eq!(
+            term("note:\"home lab\""),
+            text(Some(Field::Note), Pattern::Phrase("home lab".to_string()))
+        );
+        assert_eq!(
+            term("tech:Rust*"),
+            text(Some(Field::Tech), Pattern::Glob("rust*".chars().collect()))
+        );
+    }
+
+    #[test]
+    fn parses_negation() {
+        assert_eq!(
+            term("-aws"),
+            Term {
+                negated: true,
+                kind: Kind::Text(None, Pattern::Phrase("aws".to_string())),
+            }
+        );
+        assert!(term("-level>3").negated);
+        assert!(term("-tech:rust*").negated);
+    }
+
+    #[test]
+    fn rejects_malformed_input() {
+        assert_eq!(error("-"), "skills.error_negation");
+        assert_eq!(error("level>9"), "skills.error_level");
+        assert_eq!(error("level>=x"), "skills.error_level");
+        assert_eq!(error("years>100"), "skills.error_years");
+        assert_eq!(error("used<1800"), "skills.error_used");
+        assert_eq!(error("trend:up"), "skills.error_trend");
+        assert_eq!(error("\"home lab"), "skills.error_quote");
+        assert_eq!(error("\"a\"b\""), "skills.error_quote");
+        assert_eq!(error("\"\""), "skills.error_empty");
+        assert_eq!(error("tech:"), "skills.error_empty");
+        // One bad term fails the whole search.
+        assert_eq!(error("rust level>9"), "skills.error_level");
+    }
+
+    #[test]
+    fn ranks_against_every_term() {
+        let yew = skill("Rust: Yew", "Web Frontend", 4, "This website");
+        assert!(matches("rust level>=4", &yew));
+        assert!(!matches("rust level>4", &yew));
+        assert!(matches("years=3 used<2020 trend:fading", &yew));
+        assert!(!matches("years>3", &yew));
+        assert!(matches("cat:\"web front\"", &yew));
+        assert!(!matches("tech:\"web front\"", &yew));
+        assert!(matches("cat:web*", &yew));
+        assert!(!matches("cat:front*", &yew));
+        // Typos are tolerated in what is searched for.
+        assert!(matches("websiet", &yew));
+    }
+
+    #[test]
+    fn negated_words_match_exactly() {
+        let docker = skill("Docker", "DevOps", 4, "It just works");
+        assert!(matches("-rust", &docker));
+        assert!(matches("-dokcer", &docker));
+        assert!(!matches("-DOCKER", &docker));
+        assert!(!matches("-\"just works\"", &docker));
+        assert!(!matches("-level=4", &docker));
+        assert!(matches("-level<4", &docker));
+    }
+
+    #[test]
+    fn replaces_the_level_terms() {
+        assert_eq!(
+            Query::with_level("level>2 rust -level=1", 4),
+            "rust -level=1 level=4"
+        );
+    }
+}
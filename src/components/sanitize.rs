@@ -0,0 +1,486 @@
+use yew::prelude::*;
+use yew::virtual_dom::{VTag, VText};
+
+const ALLOWED_TAGS: &[&str] = &[
+    "a",
+    "abbr",
+    "b",
+    "blockquote",
+    "br",
+    "code",
+    "del",
+    "div",
+    "em",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "hr",
+    "i",
+    "img",
+    "kbd",
+    "li",
+    "ol",
+    "p",
+    "pre",
+    "s",
+    "span",
+    "strong",
+    "sub",
+    "sup",
+    "u",
+    "ul",
+];
+const VOID_TAGS: &[&str] = &["br", "hr", "img"];
+/// Tags removed together with everything inside them.
+const DROPPED_TAGS: &[&str] = &[
+    "script", "style", "iframe", "object", "embed", "template", "noscript", "svg", "math",
+];
+const GLOBAL_ATTRS: &[&str] = &["class", "title"];
+const TAG_ATTRS: &[(&str, &str)] = &[
+    ("a", "href"),
+    ("a", "target"),
+    ("img", "src"),
+    ("img", "alt"),
+    ("img", "width"),
+    ("img", "height"),
+    ("ol", "start"),
+];
+const URL_ATTRS: &[&str] = &["href", "src"];
+
+/// Builds a virtual DOM tree from untrusted rich content, keeping only allowlisted
+/// tags and attributes. Everything that gets removed is logged when the tree is finished.
+pub struct Sanitizer {
+    stack: Vec<VTag>,
+    dropping: Option<(String, usize)>,
+    removed: Vec<String>,
+}
+
+impl Sanitizer {
+    pub fn new(root: VTag) -> Self {
+        Self {
+            stack: vec![root],
+            dropping: None,
+            removed: Vec::new(),
+        }
+    }
+
+    pub fn open(&mut self, name: &str, attrs: &[(&str, &str)]) {
+        let name = name.to_ascii_lowercase();
+        if let Some((dropped, depth)) = &mut self.dropping {
+            if *dropped == name {
+                *depth += 1;
+            }
+            return;
+        }
+        if DROPPED_TAGS.contains(&name.as_str()) {
+            self.removed.push(format!("<{}> and its content", name));
+            self.dropping = Some((name, 1));
+            return;
+        }
+        if !ALLOWED_TAGS.contains(&name.as_str()) {
+            self.removed.push(format!("<{}>", name));
+            return;
+        }
+
+        let mut tag = VTag::new(name.clone());
+        let mut external = false;
+        for (key, value) in attrs {
+            let lower = key.to_ascii_lowercase();
+            let allowed = GLOBAL_ATTRS
+                .iter()
+                .chain(TAG_ATTRS.iter().filter(|(t, _)| *t == name).map(|(_, a)| a))
+                .find(|a| **a == lower);
+            let Some(&key) = allowed else {
+                self.removed
+                    .push(format!("{}=\"{}\" on <{}>", lower, value, name));
+                continue;
+            };
+            if URL_ATTRS.contains(&key) {
+                if !safe_url(value) {
+                    self.removed
+                        .push(format!("{}=\"{}\" on <{}>", key, value, name));
+                    continue;
+                }
+                external |= is_external(value);
+            }
+            tag.add_attribute(key, value.to_string());
+        }
+        if name == "a" && external {
+            tag.add_attribute("rel", "noopener noreferrer");
+            if !attrs.iter().any(|(k, _)| k.eq_ignore_ascii_case("target")) {
+                tag.add_attribute("target", "_blank");
+            }
+        }
+
+        if VOID_TAGS.contains(&name.as_str()) {
+            self.push(tag.into());
+        } else {
+            self.stack.push(tag);
+        }
+    }
+
+    pub fn close(&mut self, name: &str) {
+        let name = name.to_ascii_lowercase();
+        if let Some((dropped, depth)) = &mut self.dropping {
+            if *dropped == name {
+                *depth -= 1;
+                if *depth == 0 {
+                    self.dropping = None;
+                }
+            }
+            return;
+        }
+        // Unclosed children are closed implicitly; stray closing tags are ignored.
+        if let Some(pos) = self.stack.iter().skip(1).rposition(|t| t.tag() == name) {
+            while self.stack.len() > pos + 1 {
+                let tag = self.stack.pop().unwrap();
+                self.push(tag.into());
+            }
+        }
+    }
+
+    pub fn text(&mut self, text: &str) {
+        if self.dropping.is_none() {
+            self.push(VText::new(text.to_string()).into());
+        }
+    }
+
+    /// Feeds a fragment of raw HTML, which may open or close tags across several calls.
+    pub fn html(&mut self, raw: &str) {
+        let mut rest = raw;
+        while let Some(start) = rest.find('<') {
+            self.text(&decode_entities(&rest[..start]));
+            rest = &rest[start..];
+            if let Some(comment) = rest.strip_prefix("<!--") {
+                rest = comment.split_once("-->").map_or("", |(_, r)| r);
+                continue;
+            }
+            let Some(end) = tag_end(rest) else {
+                self.text(&decode_entities(rest));
+                return;
+            };
+            self.tag(&rest[1..end]);
+            rest = &rest[end + 1..];
+        }
+        self.text(&decode_entities(rest));
+    }
+
+    pub fn finish(mut self) -> Html {
+        while self.stack.len() > 1 {
+            let tag = self.stack.pop().unwrap();
+            self.push(tag.into());
+        }
+        if !self.removed.is_empty() {
+            log::warn!("sanitizer removed: {}", self.removed.join(", "));
+        }
+        self.stack.pop().unwrap().into()
+    }
+
+    fn push(&mut self, node: Html) {
+        self.stack.last_mut().unwrap().add_child(node);
+    }
+
+    fn tag(&mut self, inner: &str) {
+        if let Some(name) = inner.strip_prefix('/') {
+            self.close(name.trim());
+            return;
+        }
+        if inner.starts_with('!') || inner.starts_with('?') {
+            return;
+        }
+        let inner = inner.trim_end_matches('/');
+        let name_end = inner
+            .find(|c: char| c.is_whitespace())
+            .unwrap_or(inner.len());
+        let attrs = parse_attrs(&inner[name_end..]);
+        let attrs: Vec<(&str, &str)> = attrs.iter().map(|(k, v)| (*k, v.as_str())).collect();
+        self.open(&inner[..name_end], &attrs);
+    }
+}
+
+fn safe_url(url: &str) -> bool {
+    // Browsers ignore whitespace and control characters inside the scheme.
+    let url: String = url
+        .chars()
+        .filter(|c| !c.is_ascii_whitespace() && !c.is_ascii_control())
+        .collect::<String>()
+        .to_ascii_lowercase();
+    // Anything before the first colon that cannot be a scheme makes the URL relative.
+    match url.split_once(':') {
+        Some((scheme, _))
+            if scheme
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.')) =>
+        {
+            matches!(scheme, "http" | "https" | "mailto")
+        }
+        _ => true,
+    }
+}
+
+fn is_external(url: &str) -> bool {
+    // Browsers read backslashes in http(s) and relative URLs as slashes.
+    let url = url.trim().to_ascii_lowercase().replace('\\', "/");
+    url.starts_with("http://") || url.starts_with("https://") || url.starts_with("//")
+}
+
+/// Index of the `>` closing the tag at the start of `s`, skipping quoted attribute values.
+fn tag_end(s: &str) -> Option<usize> {
+    let mut quote = None;
+    for (i, c) in s.char_indices().skip(1) {
+        match (quote, c) {
+            (None, '"' | '\'') => quote = Some(c),
+            (Some(q), c) if q == c => quote = None,
+            (None, '>') => return Some(i),
+            _ => {}
+        }
+    }
+    None
+}
+
+fn parse_attrs(s: &str) -> Vec<(&str, String)> {
+    let mut attrs = Vec::new();
+    let mut rest = s.trim_start();
+    while !rest.is_empty() {
+        let key_end = rest
+            .find(|c: char| c == '=' || c.is_whitespace())
+            .unwrap_or(rest.len());
+        let key = &rest[..key_end];
+        rest = rest[key_end..].trim_start();
+        let value = match rest.strip_prefix('=') {
+            Some(v) => {
+                let v = v.trim_start();
+                let (value, r) = match v.chars().next() {
+                    Some(q @ ('"' | '\'')) => v[1..].split_once(q).unwrap_or((&v[1..], "")),
+                    _ => v.split_at(v.find(char::is_whitespace).unwrap_or(v.len())),
+                };
+                rest = r.trim_start();
+                decode_entities(value)
+            }
+            None => String::new(),
+        };
+        if !key.is_empty() {
+            attrs.push((key, value));
+        }
+    }
+    attrs
+}
+
+const NAMED_ENTITIES: &[(&str, char)] = &[
+    ("amp", '&'),
+    ("lt", '<'),
+    ("gt", '>'),
+    ("quot", '"'),
+    ("apos", '\''),
+    ("nbsp", '\u{a0}'),
+    ("colon", ':'),
+    ("Tab", '\t'),
+    ("NewLine", '\n'),
+];
+
+/// Decodes character references like a browser would, so that the URL checks see the
+/// same value; unknown named references are kept as they are.
+fn decode_entities(s: &str) -> String {
+    if !s.contains('&') {
+        return s.to_string();
+    }
+    let mut decoded = String::with_capacity(s.len());
+    let mut rest = s;
+    while let Some(amp) = rest.find('&') {
+        decoded.push_str(&rest[..amp]);
+        rest = &rest[amp..];
+        match entity(rest) {
+            Some((c, len)) => {
+                decoded.push(c);
+                rest = &rest[len..];
+            }
+            None => {
+                decoded.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    decoded.push_str(rest);
+    decoded
+}
+
+/// The character referenced at the start of `s` and the length of the reference.
+/// Numeric references may leave out the semicolon, as browsers accept that too.
+fn entity(s: &str) -> Option<(char, usize)> {
+    let body = s.strip_prefix('&')?;
+    let Some(number) = body.strip_prefix('#') else {
+        return NAMED_ENTITIES.iter().find_map(|(name, c)| {
+            body.strip_prefix(name)?
+                .starts_with(';')
+                .then_some((*c, name.len() + 2))
+        });
+    };
+    let (radix, digits) = match number.strip_prefix(['x', 'X']) {
+        Some(hex) => (16, hex),
+        None => (10, number),
+    };
+    let len = digits
+        .find(|c: char| !c.is_digit(radix))
+        .unwrap_or(digits.len());
+    if len == 0 {
+        return None;
+    }
+    let c = u32::from_str_radix(&digits[..len], radix)
+        .ok()
+        .and_then(char::from_u32)
+        .filter(|c| *c != '\0')
+        .unwrap_or('\u{fffd}');
+    let semicolon = digits[len..].starts_with(';') as usize;
+    Some((c, s.len() - digits.len() + len + semicolon))
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use yew::virtual_dom::VNode;
+
+    /// The tree as HTML, with attributes sorted and text escaped.
+    pub(crate) fn markup(node: &Html) -> String {
+        match node {
+            VNode::VTag(tag) => {
+                let mut attrs: Vec<(&str, &str)> = tag.attributes.iter().collect();
+                attrs.sort();
+                let attrs: String = attrs
+                    .iter()
+                    .map(|(k, v)| format!(" {}=\"{}\"", k, v))
+                    .collect();
+                let children: String = tag.children().iter().map(markup).collect();
+                match VOID_TAGS.contains(&tag.tag()) {
+                    true => format!("<{}{}>", tag.tag(), attrs),
+                    false => format!("<{0}{1}>{2}</{0}>", tag.tag(), attrs, children),
+                }
+            }
+            VNode::VText(text) => text.text.replace('<', "&lt;"),
+            VNode::VList(list) => list.iter().map(markup).collect(),
+            _ => String::new(),
+        }
+    }
+
+    fn clean(raw: &str) -> String {
+        let mut sanitizer = Sanitizer::new(VTag::new("div"));
+        sanitizer.html(raw);
+        let html = markup(&sanitizer.finish());
+        html["<div>".len()..html.len() - "</div>".len()].to_string()
+    }
+
+    #[test]
+    fn keeps_allowed_markup() {
+        assert_eq!(
+            clean("<p class=\"x\">a <strong>b</strong><br/>c &amp; d</p>"),
+            "<p class=\"x\">a <strong>b</strong><br>c & d</p>"
+        );
+    }
+
+    #[test]
+    fn drops_script_and_style_with_their_content() {
+        assert_eq!(clean("<p>a<script>alert(1)</script>b</p>"), "<p>ab</p>");
+        assert_eq!(clean("a<STYLE>p { color: red }</Style>b"), "ab");
+        assert_eq!(clean("a<script><script></script>alert(1)</script>b"), "ab");
+        assert_eq!(clean("a<script>alert(1)"), "a");
+        assert_eq!(clean("<svg><script>alert(1)</script></svg>b"), "b");
+    }
+
+    #[test]
+    fn strips_event_handlers() {
+        assert_eq!(
+            clean("<img src=\"a.png\" onerror=\"alert(1)\" alt=a>"),
+            "<img alt=\"a\" src=\"a.png\">"
+        );
+        assert_eq!(
+            clean("<p ONCLICK='alert(1)' onmouseover=alert(1)>x</p>"),
+            "<p>x</p>"
+        );
+        assert_eq!(
+            clean("<a href=\"/x\" style=\"x\">x</a>"),
+            "<a href=\"/x\">x</a>"
+        );
+    }
+
+    #[test]
+    fn blocks_script_urls() {
+        for href in [
+            "javascript:alert(1)",
+            "JaVaScRiPt:alert(1)",
+            " javascript:alert(1)",
+            "java\tscript:alert(1)",
+            "java\nscript:alert(1)",
+            "\u{1}javascript:alert(1)",
+            "&#106;avascript:alert(1)",
+            "&#0000106avascript:alert(1)",
+            "&#x6A;avascript:alert(1)",
+            "java&#x09;script:alert(1)",
+            "java&Tab;script:alert(1)",
+            "javascript&colon;alert(1)",
+            "vbscript:msgbox(1)",
+            "data:text/html,<script>alert(1)</script>",
+        ] {
+            let html = clean(&format!("<a href=\"{}\">x</a>", href));
+            assert_eq!(html, "<a>x</a>", "{:?}", href);
+        }
+        assert_eq!(clean("<img src=\"javascript:alert(1)\">"), "<img>");
+    }
+
+    #[test]
+    fn allows_safe_urls() {
+        for href in [
+            "/projects",
+            "#top",
+            "page?at=10:30",
+            "mailto:someone@example.com",
+        ] {
+            let html = clean(&format!("<a href=\"{}\">x</a>", href));
+            assert_eq!(html, format!("<a href=\"{}\">x</a>", href));
+        }
+    }
+
+    #[test]
+    fn closes_unclosed_tags_and_ignores_stray_ones() {
+        assert_eq!(clean("<p><em>x</p>y"), "<p><em>x</em></p>y");
+        assert_eq!(clean("<b>a<i>b</b>c"), "<b>a<i>b</i></b>c");
+        assert_eq!(clean("</div>x</em>"), "x");
+        assert_eq!(clean("<blink>x</blink>"), "x");
+        assert_eq!(clean("a <b"), "a &lt;b");
+        assert_eq!(clean("<p title=\"a>b\">x</p>"), "<p title=\"a>b\">x</p>");
+    }
+
+    #[test]
+    fn removes_comments() {
+        assert_eq!(clean("a<!-- <script>alert(1)</script> -->b"), "ab");
+        assert_eq!(clean("a<!-- never closed <b>x</b>"), "a");
+        assert_eq!(clean("<!DOCTYPE html>a<?xml?>"), "a");
+    }
+
+    #[test]
+    fn external_links_open_safely() {
+        assert_eq!(
+            clean("<a href=\"https://example.com\" rel=\"opener\">x</a>"),
+            "<a href=\"https://example.com\" rel=\"noopener noreferrer\" target=\"_blank\">x</a>"
+        );
+        assert_eq!(
+            clean("<a href=\"//example.com\" target=\"_self\">x</a>"),
+            "<a href=\"//example.com\" rel=\"noopener noreferrer\" target=\"_self\">x</a>"
+        );
+        assert_eq!(
+            clean("<a href=\"/\\example.com\">x</a>"),
+            "<a href=\"/\\example.com\" rel=\"noopener noreferrer\" target=\"_blank\">x</a>"
+        );
+        assert_eq!(clean("<a href=\"/cv\">x</a>"), "<a href=\"/cv\">x</a>");
+    }
+
+    #[test]
+    fn decodes_character_references() {
+        assert_eq!(
+            decode_entities("&lt;&#65;&#x42;&#67&unknown; &"),
+            "<ABC&unknown; &"
+        );
+        assert_eq!(decode_entities("&#0;&#99999999999;"), "\u{fffd}\u{fffd}");
+    }
+}
@@ -1,85 +1,175 @@
-use gloo_timers::future::TimeoutFuture;
+use crate::components::charts;
+use crate::components::content;
+use crate::components::export::{self, Format};
+use crate::components::fuzzy::{self, Ranked};
+use crate::components::matcher::PostingMatcher;
+use crate::components::query::{ParseError, Query};
+use crate::components::transition::{Ticket, Transition, SKILL_ROW_EXIT_MS};
+use crate::i18n::{Locale, LocaleProps};
+use crate::manifest::{self, Content, Portfolio, Skill, Trend};
+use crate::pages::cv::CvQuery;
+use crate::Route;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::collections::HashMap;
 use std::rc::Rc;
-use wasm_bindgen_futures::spawn_local;
 use web_sys::HtmlInputElement;
 use yew::prelude::*;
 use yew::Properties;
+use yew_router::history::{History, HistoryListener, Location};
+use yew_router::prelude::*;
 
-pub enum Msg {
-    Search,
-    SortByCateg,
-    SortByTech,
-    SortByLevel,
-    SetSort(Sort),
+/// The query string of the skills page, e.g.
+/// `/skills?q=rust&sort=-level,tech&tags=Web,FrontEnd&op=or&view=radar`.
+#[derive(Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SkillQuery {
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub q: String,
+    /// Comma-separated category facets.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub tags: String,
+    #[serde(default, skip_serializing_if = "FacetMode::is_and")]
+    pub op: FacetMode,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sort: Option<Sort>,
+    #[serde(default, skip_serializing_if = "SkillView::is_table")]
+    pub view: SkillView,
+}
+
+impl SkillQuery {
+    /// The table searched for one technology, as a phrase in the technology column, so that
+    /// short names such as "Go" or "C" do not also find unrelated skills fuzzily.
+    pub fn technology(name: &str) -> Self {
+        SkillQuery {
+            q: format!("tech:\"{}\"", name),
+            ..Default::default()
+        }
+    }
 }
 
-#[derive(Eq, PartialEq, Clone)]
-pub struct Level(usize);
+/// The table, one of the charts of the skills matching the search and the facets,
+/// or the job posting matcher.
+#[derive(Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SkillView {
+    #[default]
+    Table,
+    Radar,
+    Histogram,
+    Match,
+}
+
+impl SkillView {
+    fn is_table(&self) -> bool {
+        *self == SkillView::Table
+    }
+}
+
+/// How selected category facets combine: a skill needs all of them, or any of them.
+#[derive(Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FacetMode {
+    #[default]
+    And,
+    Or,
+}
 
-impl std::fmt::Display for Level {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", "✫".repeat(self.0.min(5)))
+impl FacetMode {
+    fn is_and(&self) -> bool {
+        *self == FacetMode::And
     }
 }
 
-#[derive(Eq, PartialEq, Clone)]
-pub struct Skill {
-    category: String,
-    technology: String,
-    level: Level,
-    note: String,
+/// A job or project where a skill was used.
+#[derive(PartialEq)]
+pub struct Usage {
+    pub label: String,
+    pub route: Route,
+    /// For jobs, the CV opened on the job.
+    pub query: Option<CvQuery>,
+}
+
+pub enum Msg {
+    Content(Content),
+    Retry,
+    Search,
+    /// Sorts on a column, or adds it as another sort key when shift is held.
+    SortBy(Column, bool),
+    SetSort(Ticket),
+    ToggleTag(String),
+    ToggleMode,
+    ToggleDetails,
+    SetView(SkillView),
+    /// Shows the table filtered on a category picked in a chart.
+    SelectTag(String),
+    /// Shows the table filtered on a level picked in a chart.
+    SelectLevel(usize),
+    Export(Format),
+    UrlChanged,
 }
 
-#[derive(Eq, PartialEq, Properties)]
+#[derive(PartialEq, Properties)]
 pub struct SkillCardProps {
     skill: Rc<Skill>,
-    search: Rc<String>,
+    query: Rc<Query>,
+    in_facets: bool,
+    /// Shows the years, last used and trend columns.
+    details: bool,
+    used_in: Rc<Vec<Usage>>,
+    locale: Locale,
 }
 
 pub struct SkillCard {
+    hit: Option<Ranked>,
     visible: bool,
+    expanded: bool,
+}
+
+fn details_width(details: bool, without: &'static str, with: &'static str) -> &'static str {
+    match details {
+        true => with,
+        false => without,
+    }
+}
+
+fn trend_arrow(trend: Trend) -> &'static str {
+    match trend {
+        Trend::Rising => "↗",
+        Trend::Stable => "→",
+        Trend::Fading => "↘",
+    }
+}
+
+fn in_facets(skill: &Skill, tags: &[String], mode: FacetMode) -> bool {
+    let has = |tag: &String| skill.categories.contains(tag);
+    match mode {
+        _ if tags.is_empty() => true,
+        FacetMode::And => tags.iter().all(has),
+        FacetMode::Or => tags.iter().any(has),
+    }
 }
 
 impl Component for SkillCard {
     type Message = ();
     type Properties = SkillCardProps;
 
-    fn create(_ctx: &Context<Self>) -> Self {
-        Self { visible: true }
+    fn create(ctx: &Context<Self>) -> Self {
+        let hit = ctx.props().query.rank(&ctx.props().skill);
+        Self {
+            visible: ctx.props().in_facets && hit.is_some(),
+            hit,
+            expanded: false,
+        }
+    }
+
+    fn update(&mut self, _ctx: &Context<Self>, _msg: Self::Message) -> bool {
+        self.expanded = !self.expanded;
+        true
     }
 
     fn changed(&mut self, ctx: &Context<Self>) -> bool {
-        if ctx
-            .props()
-            .skill
-            .technology
-            .to_lowercase()
-            .contains(&ctx.props().search.to_string().to_lowercase())
-        {
-            self.visible = true;
-            return true;
-        }
-        if ctx
-            .props()
-            .skill
-            .category
-            .to_lowercase()
-            .contains(&ctx.props().search.to_string().to_lowercase())
-        {
-            self.visible = true;
-            return true;
-        }
-        if ctx
-            .props()
-            .skill
-            .note
-            .to_lowercase()
-            .contains(&ctx.props().search.to_string().to_lowercase())
-        {
-            self.visible = true;
-            return true;
-        }
-        self.visible = false;
+        self.hit = ctx.props().query.rank(&ctx.props().skill);
+        self.visible = ctx.props().in_facets && self.hit.is_some();
         true
     }
 
@@ -88,268 +178,820 @@ impl Component for SkillCard {
             true => "skill-visible",
             false => "skill-hidden",
         };
+        let used_in = &ctx.props().used_in;
+        let (used_class, onclick) = match used_in.is_empty() {
+            true => ("", None),
+            false => ("skill-used", Some(ctx.link().callback(|_| ()))),
+        };
+        let skill = &ctx.props().skill;
+        let locale = ctx.props().locale;
+        let details = ctx.props().details;
+        let marked = |field: usize, text: &str| match &self.hit {
+            Some(hit) => fuzzy::highlight(text, &hit.positions[field]),
+            None => html! { {text} },
+        };
         html! {
-            <div class={classes!("columns", "is-mobile", "is-gapless", "is-marginless", skill_class)}>
+            <div class={classes!(skill_class)}>
+            <div class={classes!("columns", "is-mobile", "is-gapless", "is-marginless", used_class)}
+                {onclick}>
               <div class="column is-6"><div class="columns is-gapless is-marginless skill-group">
-                <div class="column is-7 skill-category">{ctx.props().skill.category.clone()}</div>
-                <div class="column is-5 skill-text">{ctx.props().skill.technology.clone()}</div>
+                <div class="column is-7 skill-category">{marked(1, &skill.categories.join(", "))}</div>
+                <div class="column is-5 skill-text">{marked(0, &skill.technology)}</div>
               </div></div>
               <div class="column is-6"><div class="columns is-gapless is-marginless">
-                <div class="column is-3 is-size-6 skill-level">{ctx.props().skill.level.clone()}</div>
-                <div class="column is-9 gray4 has-text-right skill-desc">{ctx.props().skill.note.clone()}</div>
+                <div class={classes!("column", details_width(details, "is-3", "is-2"), "is-size-6", "skill-level")}>
+                    {skill.level.clone()}
+                </div>
+                if details {
+                    <div class="column is-2 skill-detail">
+                        {skill.years.map(|y| locale.duration(y as u32 * 12)).unwrap_or_default()}
+                    </div>
+                    <div class="column is-2 skill-detail">
+                        {skill.last_used.map(|y| y.to_string()).unwrap_or_default()}
+                    </div>
+                    <div class="column is-1 skill-detail">
+                        if let Some(trend) = skill.trend {
+                            <span title={locale.t(&format!("skills.{}", trend.key()))}>
+                                {trend_arrow(trend)}
+                            </span>
+                        }
+                    </div>
+                }
+                <div class={classes!("column", details_width(details, "is-9", "is-5"), "gray4", "has-text-right", "skill-desc")}>
+                    {marked(2, &skill.note)}
+                </div>
               </div></div>
             </div>
+            if self.expanded {
+                <div class="skill-usage">
+                    <span class="gray4">{locale.t("skills.used_in")}</span>
+                    { for used_in.iter().map(|u| html! {
+                        <Link<Route, CvQuery> classes={classes!("tag", "is-black")}
+                            to={u.route.clone()} query={u.query.clone()}>
+                            {&u.label}
+                        </Link<Route, CvQuery>>
+                    }) }
+                </div>
+            }
+            </div>
         }
     }
 }
 
-#[derive(PartialEq)]
-pub enum Sort {
-    ByCateg,
-    ByTech,
-    ByLevel,
+#[derive(Clone, Copy, PartialEq)]
+pub enum Column {
+    Category,
+    Tech,
+    Level,
+    Years,
+    LastUsed,
+    Trend,
+}
+
+impl Column {
+    const ALL: [Column; 6] = [
+        Column::Category,
+        Column::Tech,
+        Column::Level,
+        Column::Years,
+        Column::LastUsed,
+        Column::Trend,
+    ];
+
+    fn key(self) -> &'static str {
+        match self {
+            Column::Category => "category",
+            Column::Tech => "tech",
+            Column::Level => "level",
+            Column::Years => "years",
+            Column::LastUsed => "used",
+            Column::Trend => "trend",
+        }
+    }
+
+    fn is_detail(self) -> bool {
+        matches!(self, Column::Years | Column::LastUsed | Column::Trend)
+    }
+
+    /// Whether the skill has a value in this column; skills without one sort last.
+    fn has(self, skill: &Skill) -> bool {
+        match self {
+            Column::Years => skill.years.is_some(),
+            Column::LastUsed => skill.last_used.is_some(),
+            Column::Trend => skill.trend.is_some(),
+            _ => true,
+        }
+    }
+
+    fn compare(self, a: &Skill, b: &Skill) -> Ordering {
+        match self {
+            Column::Category => a.categories.cmp(&b.categories),
+            Column::Tech => a
+                .technology
+                .to_lowercase()
+                .cmp(&b.technology.to_lowercase()),
+            Column::Level => a.level.0.cmp(&b.level.0),
+            Column::Years => a.years.cmp(&b.years),
+            Column::LastUsed => a.last_used.cmp(&b.last_used),
+            Column::Trend => a.trend.cmp(&b.trend),
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub struct SortKey {
+    column: Column,
+    descending: bool,
+}
+
+/// Sort keys by priority, written as `-level,tech` in the URL and in local storage:
+/// a leading `-` sorts descending and `none` keeps the manifest order.
+/// Skills that compare equal on every key stay in manifest order.
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
+pub struct Sort(Vec<SortKey>);
+
+const SORT_STORAGE_KEY: &str = "skills.sort";
+
+impl Default for Sort {
+    fn default() -> Self {
+        Sort(vec![SortKey {
+            column: Column::Level,
+            descending: true,
+        }])
+    }
+}
+
+/// Unknown column names are skipped rather than failing the whole query string.
+impl From<String> for Sort {
+    fn from(s: String) -> Self {
+        Sort(
+            s.split(',')
+                .filter_map(|key| {
+                    let (descending, name) = match key.strip_prefix('-') {
+                        Some(name) => (true, name),
+                        None => (false, key),
+                    };
+                    let column = *Column::ALL.iter().find(|c| c.key() == name)?;
+                    Some(SortKey { column, descending })
+                })
+                .collect(),
+        )
+    }
+}
+
+impl From<Sort> for String {
+    fn from(sort: Sort) -> Self {
+        match sort.0.is_empty() {
+            true => "none".to_string(),
+            false => sort
+                .0
+                .iter()
+                .map(|k| match k.descending {
+                    true => format!("-{}", k.column.key()),
+                    false => k.column.key().to_string(),
+                })
+                .collect::<Vec<_>>()
+                .join(","),
+        }
+    }
+}
+
+impl Sort {
+    fn is_default(&self) -> bool {
+        *self == Sort::default()
+    }
+
+    /// Clicking a column cycles it through ascending, descending and off. A plain click
+    /// makes it the only key, `add` (a shift-click) keeps the other keys.
+    fn toggled(&self, column: Column, add: bool) -> Sort {
+        let position = self.0.iter().position(|k| k.column == column);
+        let current = match add || self.0.len() == 1 {
+            true => position.map(|i| self.0[i].descending),
+            false => None,
+        };
+        let next = match current {
+            None => Some(false),
+            Some(false) => Some(true),
+            Some(true) => None,
+        };
+        let mut keys = match add {
+            true => self.0.clone(),
+            false => Vec::new(),
+        };
+        match (position.filter(|_| add), next) {
+            (Some(i), Some(descending)) => keys[i].descending = descending,
+            (Some(i), None) => {
+                keys.remove(i);
+            }
+            (None, Some(descending)) => keys.push(SortKey { column, descending }),
+            (None, None) => {}
+        }
+        Sort(keys)
+    }
+
+    fn compare(&self, a: &Skill, b: &Skill) -> Ordering {
+        self.0
+            .iter()
+            .map(|k| match (k.column.has(a), k.column.has(b), k.descending) {
+                (true, false, _) => Ordering::Less,
+                (false, true, _) => Ordering::Greater,
+                (_, _, true) => k.column.compare(b, a),
+                (_, _, false) => k.column.compare(a, b),
+            })
+            .find(|o| o.is_ne())
+            .unwrap_or(Ordering::Equal)
+    }
+
+    fn has_detail(&self) -> bool {
+        self.0.iter().any(|k| k.column.is_detail())
+    }
+
+    /// The direction and priority (from 1) of the column, if it is a sort key.
+    fn key(&self, column: Column) -> Option<(bool, usize)> {
+        let i = self.0.iter().position(|k| k.column == column)?;
+        Some((self.0[i].descending, i + 1))
+    }
+
+    fn stored() -> Option<Sort> {
+        let storage = web_sys::window()?.local_storage().ok()??;
+        Some(Sort::from(storage.get_item(SORT_STORAGE_KEY).ok()??))
+    }
+
+    fn store(&self) {
+        if let Some(Ok(Some(storage))) = web_sys::window().map(|w| w.local_storage()) {
+            let _ = storage.set_item(SORT_STORAGE_KEY, &String::from(self.clone()));
+        }
+    }
 }
 
 pub struct SkillList {
-    skills: Vec<Rc<Skill>>,
-    search: Rc<String>,
+    content: Content,
+    skills: Rc<Vec<Rc<Skill>>>,
+    categories: Vec<(String, usize)>,
+    tags: Vec<String>,
+    mode: FacetMode,
+    usage: HashMap<String, Rc<Vec<Usage>>>,
+    query: Rc<Query>,
+    query_error: Option<ParseError>,
+    /// Hides every row while the table is re-sorted.
+    sorting: Transition<Sort>,
+    details: bool,
+    view: SkillView,
     search_input: NodeRef,
+    /// Search from the URL, written into the input once it is rendered.
+    initial_search: Option<String>,
     sort: Sort,
+    _listener: Option<HistoryListener>,
 }
 
 impl Component for SkillList {
     type Message = Msg;
 
-    type Properties = ();
+    type Properties = LocaleProps;
 
-    fn create(_ctx: &Context<Self>) -> Self {
+    fn create(ctx: &Context<Self>) -> Self {
+        let content = manifest::load(ctx.link().callback(Msg::Content));
+        let locale = ctx.props().locale;
+        let query = SkillList::url_query(ctx);
+        let search = query.q;
+        let parsed = Query::parse(&search);
         SkillList {
-            skills: skills_vector(),
-            search: Rc::new("".to_string()),
+            skills: match &content {
+                Content::Ready(p) => skills_vector(p, locale),
+                _ => Rc::default(),
+            },
+            categories: match &content {
+                Content::Ready(p) => p.categories(),
+                _ => Vec::new(),
+            },
+            tags: split_tags(&query.tags),
+            mode: query.op,
+            usage: match &content {
+                Content::Ready(p) => usage_map(p, locale),
+                _ => HashMap::new(),
+            },
+            content,
+            query: Rc::new(parsed.clone().unwrap_or_default()),
+            query_error: parsed.err(),
+            sorting: Transition::default(),
+            details: false,
+            view: query.view,
             search_input: NodeRef::default(),
-            sort: Sort::ByLevel,
+            initial_search: Some(search).filter(|s| !s.is_empty()),
+            sort: query.sort.or_else(Sort::stored).unwrap_or_default(),
+            _listener: ctx.link().history().map(|h| {
+                let link = ctx.link().clone();
+                h.listen(move || link.send_message(Msg::UrlChanged))
+            }),
         }
     }
 
     fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
-        macro_rules! sort {
-            ($key:ident) => {
-                if self.sort != Sort::$key {
-                    let ms = match *self.search == "".to_string() {
-                        true => 400,
-                        false => 310,
-                    };
-                    self.search = Rc::new("DELETE_ALL_ITEMS".to_string());
-                    let link = ctx.link().clone();
-                    spawn_local(async move {
-                        TimeoutFuture::new(ms).await;
-                        link.send_message(Msg::SetSort(Sort::$key));
-                    });
-                }
-            };
-        }
         match msg {
+            Msg::Content(content) => {
+                if let Content::Ready(p) = &content {
+                    self.skills = skills_vector(p, ctx.props().locale);
+                    self.usage = usage_map(p, ctx.props().locale);
+                    self.categories = p.categories();
+                }
+                self.content = content;
+            }
+            Msg::Retry => {
+                self.content = manifest::load(ctx.link().callback(Msg::Content));
+            }
             Msg::Search => {
-                self.search = Rc::new(
-                    self.search_input
-                        .cast::<HtmlInputElement>()
-                        .unwrap()
-                        .value(),
-                );
+                let search = self
+                    .search_input
+                    .cast::<HtmlInputElement>()
+                    .unwrap()
+                    .value();
+                self.set_search(&search);
+                self.update_url(ctx, false);
+            }
+            Msg::SortBy(column, add) => {
+                // Clicks during the exit build on the sort that is about to be applied.
+                let sort = self.shown_sort().toggled(column, add);
+                if sort == self.sort {
+                    self.sorting.cancel();
+                } else {
+                    self.sorting
+                        .start_after(sort, SKILL_ROW_EXIT_MS, ctx.link(), Msg::SetSort);
+                }
+            }
+            Msg::SetSort(ticket) => {
+                let Some(sort) = self.sorting.finish(ticket) else {
+                    return false;
+                };
+                sort.store();
+                self.sort = sort;
+                self.update_url(ctx, true);
+            }
+            Msg::ToggleTag(tag) => {
+                match self.tags.iter().position(|t| *t == tag) {
+                    Some(i) => {
+                        self.tags.remove(i);
+                    }
+                    None => self.tags.push(tag),
+                }
+                self.update_url(ctx, true);
             }
-            Msg::SortByCateg => {
-                sort!(ByCateg);
+            Msg::ToggleDetails => self.details = !self.details,
+            Msg::SetView(view) => {
+                self.view = view;
+                self.update_url(ctx, true);
             }
-            Msg::SortByTech => {
-                sort!(ByTech);
+            Msg::SelectTag(tag) => {
+                if !self.tags.contains(&tag) {
+                    self.tags.push(tag);
+                }
+                self.view = SkillView::Table;
+                self.update_url(ctx, true);
+            }
+            Msg::Export(format) => {
+                let rows: Vec<&Skill> = self
+                    .ranked()
+                    .into_iter()
+                    .filter(|s| in_facets(s, &self.tags, self.mode) && self.query.rank(s).is_some())
+                    .map(|s| s.as_ref())
+                    .collect();
+                export::download(format, &export::render(format, &rows, &self.filters()));
+                return false;
+            }
+            Msg::SelectLevel(level) => {
+                if let Some(input) = self.search_input.cast::<HtmlInputElement>() {
+                    let search = Query::with_level(&input.value(), level);
+                    input.set_value(&search);
+                    self.set_search(&search);
+                }
+                self.view = SkillView::Table;
+                self.update_url(ctx, true);
             }
-            Msg::SortByLevel => {
-                sort!(ByLevel);
+            Msg::ToggleMode => {
+                self.mode = match self.mode {
+                    FacetMode::And => FacetMode::Or,
+                    FacetMode::Or => FacetMode::And,
+                };
+                self.update_url(ctx, true);
             }
-            Msg::SetSort(s) => {
-                self.sort = s;
-                match self.sort {
-                    Sort::ByCateg => self.skills.sort_by(|a, b| a.category.cmp(&b.category)),
-                    Sort::ByTech => self.skills.sort_by(|a, b| a.technology.cmp(&b.technology)),
-                    Sort::ByLevel => self.skills.sort_by(|a, b| b.level.0.cmp(&a.level.0)),
+            Msg::UrlChanged => {
+                let query = SkillList::url_query(ctx);
+                if query == self.url_state() {
+                    return false;
+                }
+                if let Some(input) = self.search_input.cast::<HtmlInputElement>() {
+                    input.set_value(&query.q);
                 }
-                ctx.link().send_message(Msg::Search);
+                self.set_search(&query.q);
+                self.view = query.view;
+                self.tags = split_tags(&query.tags);
+                self.mode = query.op;
+                self.sort = query.sort.or_else(Sort::stored).unwrap_or_default();
+                self.sorting.cancel();
             }
         }
         true
     }
 
+    fn changed(&mut self, ctx: &Context<Self>) -> bool {
+        if let Content::Ready(p) = &self.content {
+            self.skills = skills_vector(p, ctx.props().locale);
+            self.usage = usage_map(p, ctx.props().locale);
+        }
+        true
+    }
+
+    fn rendered(&mut self, _ctx: &Context<Self>, _first_render: bool) {
+        if let Some(input) = self.search_input.cast::<HtmlInputElement>() {
+            if let Some(search) = self.initial_search.take() {
+                input.set_value(&search);
+            }
+        }
+    }
+
     fn view(&self, ctx: &Context<Self>) -> Html {
+        let locale = ctx.props().locale;
+        let details = self.show_details();
+        let view_button = |view: SkillView, label: String| {
+            let class = match self.view == view {
+                true => "is-white",
+                false => "is-black",
+            };
+            html! {
+                <a class={classes!("tag", class)} onclick={ctx.link().callback(move |_| Msg::SetView(view))}>
+                    {label}
+                </a>
+            }
+        };
         macro_rules! show_table_head {
-            ($class:expr, $msg:expr, $sort:expr, $text:expr) => {{
+            ($class:expr, $column:ident, $text:expr) => {{
                 html! {
                     <div class={$class}>
-                        <a class="has-text-light has-text-weight-bold"
-                        onclick={ctx.link().callback(|_| Msg::$msg)}>
+                        <a class="has-text-light has-text-weight-bold" title={locale.t("skills.sort_hint")}
+                        onclick={ctx.link().callback(|e: MouseEvent| Msg::SortBy(Column::$column, e.shift_key()))}>
                             {$text}
-                            if self.sort == Sort::$sort {
-                                {" ▾"}
+                            if let Some((descending, priority)) = self.shown_sort().key(Column::$column) {
+                                {if descending { " ▾" } else { " ▴" }}
+                                if self.shown_sort().0.len() > 1 {
+                                    <sup class="sortpriority">{priority}</sup>
+                                }
                             }
                         </a>
                     </div>
                 }
             }};
         }
+        if !matches!(self.content, Content::Ready(_)) {
+            return content::placeholder(
+                &self.content,
+                locale,
+                12,
+                ctx.link().callback(|_| Msg::Retry),
+            );
+        }
         html! {
             <div class="container has-text-left" style="max-width: 1030px">
                 <div class="has-text-centered"><input
                     class="input is-rounded is-white has-text-centered is-small is-overlay"
                     style="max-width: 250px"
-                    placeholder="Type to search..."
+                    placeholder={locale.t("skills.search")}
+                    title={locale.t("skills.syntax")}
                     type="text" ref={self.search_input.clone()}
                     onkeyup={ctx.link().callback(|_| Msg::Search)}
                 /></div>
-                <br/>
-                <div style="border-bottom: 2px solid #000000; background-color: #000f0f;
-                            padding: 5px 15px 5px 15px; border-radius: 20px">
-                  <div class="columns is-mobile is-gapless is-marginless is-clearfix">
-                     <div class="column is-6"><div class="columns is-gapless is-marginless skill-group">
-                        {show_table_head!("column is-7 skill-category", SortByCateg, ByCateg, "category")}
-                        {show_table_head!("column is-5 skill-title", SortByTech, ByTech, "technology")}
-                     </div></div>
-                     <div class="column is-6"><div class="columns is-gapless is-marginless">
-                        {show_table_head!("column is-3 skill-level", SortByLevel, ByLevel, "level")}
-                        <div class="column is-9 gray4 skill-desc">
-                            <div class="has-text-weight-bold has-text-right">{"note"}</div>
-                        </div>
-                     </div></div>
-                  </div>
+                if let Some(e) = &self.query_error {
+                    <p class="help has-text-centered queryerror">
+                        {locale.t(e.key)} {" "} <code>{&e.token}</code>
+                    </p>
+                }
+                <div class="level is-mobile skilldetails">
+                    <div class="level-left tags has-addons">
+                        {view_button(SkillView::Table, locale.t("skills.table"))}
+                        {view_button(SkillView::Radar, locale.t("skills.radar"))}
+                        {view_button(SkillView::Histogram, locale.t("skills.histogram"))}
+                        {view_button(SkillView::Match, locale.t("skills.match"))}
+                    </div>
+                    <div class="level-right tags">
+                        <span class="gray4">{locale.t("skills.export")}</span>
+                        { for Format::ALL.iter().map(|format| {
+                            let format = *format;
+                            html! {
+                                <a class="tag is-black is-rounded" title={locale.t("skills.export_hint")}
+                                    onclick={ctx.link().callback(move |_| Msg::Export(format))}>
+                                    {format.label()}
+                                </a>
+                            }
+                        }) }
+                        if self.view == SkillView::Table && !self.shown_sort().has_detail() {
+                            <a class="tag is-black is-rounded" onclick={ctx.link().callback(|_| Msg::ToggleDetails)}>
+                                {match details {
+                                    true => locale.t("skills.fewer_columns"),
+                                    false => locale.t("skills.more_columns"),
+                                }}
+                            </a>
+                        }
+                    </div>
                 </div>
-                <div style="padding: 0px 15px 0px 15px">
-                    { for self.skills.iter().map(|s|
-                        html! { <SkillCard skill={s.clone()} search={self.search.clone()}/> }
-                    ) }
+                <div class="tags is-centered skillfacets">
+                    if !self.tags.is_empty() {
+                        <a class="tag is-white is-rounded" onclick={ctx.link().callback(|_| Msg::ToggleMode)}>
+                            {match self.mode {
+                                FacetMode::And => locale.t("skills.all_of"),
+                                FacetMode::Or => locale.t("skills.any_of"),
+                            }}
+                        </a>
+                    }
+                    { for self.categories.iter().map(|(category, count)| {
+                        let class = match self.tags.contains(category) {
+                            true => "is-white",
+                            false => "is-black",
+                        };
+                        let tag = category.clone();
+                        html! {
+                            <a class={classes!("tag", "is-rounded", class)}
+                                onclick={ctx.link().callback(move |_| Msg::ToggleTag(tag.clone()))}>
+                                {category} <span class="facetcount">{count}</span>
+                            </a>
+                        }
+                    }) }
                 </div>
+                if self.view == SkillView::Radar {
+                    {charts::radar(&self.matching(), locale, ctx.link().callback(Msg::SelectTag))}
+                } else if self.view == SkillView::Match {
+                    <PostingMatcher skills={self.skills.clone()} {locale}/>
+                } else if self.view == SkillView::Histogram {
+                    {charts::histogram(&self.matching(), locale, ctx.link().callback(Msg::SelectLevel))}
+                } else {
+                    <div style="border-bottom: 2px solid #000000; background-color: #000f0f;
+                                padding: 5px 15px 5px 15px; border-radius: 20px">
+                      <div class="columns is-mobile is-gapless is-marginless is-clearfix">
+                         <div class="column is-6"><div class="columns is-gapless is-marginless skill-group">
+                            {show_table_head!("column is-7 skill-category", Category, locale.t("skills.category"))}
+                            {show_table_head!("column is-5 skill-title", Tech, locale.t("skills.technology"))}
+                         </div></div>
+                         <div class="column is-6"><div class="columns is-gapless is-marginless">
+                            {show_table_head!(classes!("column", details_width(details, "is-3", "is-2"), "skill-level"),
+                                Level, locale.t("skills.level"))}
+                            if details {
+                                {show_table_head!("column is-2 skill-detail", Years, locale.t("skills.years"))}
+                                {show_table_head!("column is-2 skill-detail", LastUsed, locale.t("skills.last_used"))}
+                                {show_table_head!("column is-1 skill-detail", Trend, locale.t("skills.trend"))}
+                            }
+                            <div class={classes!("column", details_width(details, "is-9", "is-5"), "gray4", "skill-desc")}>
+                                <div class="has-text-weight-bold has-text-right">{locale.t("skills.note")}</div>
+                            </div>
+                         </div></div>
+                      </div>
+                    </div>
+                    <div style="padding: 0px 15px 0px 15px">
+                        { for self.ranked().into_iter().map(|s| html! {
+                            <SkillCard skill={s.clone()} query={self.query.clone()} {locale}
+                                {details} in_facets={!self.sorting.is_exiting() && in_facets(s, &self.tags, self.mode)}
+                                used_in={self.usage.get(&s.technology).cloned().unwrap_or_default()}/>
+                        }) }
+                    </div>
+                }
             </div>
         }
     }
 }
 
-fn skills_vector() -> Vec<Rc<Skill>> {
-    macro_rules! skills_vec {
-            ($({ $category:literal, $name:literal, $level:literal, $note:literal },)*) => {
-                vec![
-                    $(
-                        Rc::new(Skill {
-                            category: $category.to_string(),
-                            technology: $name.to_string(),
-                            level: Level($level),
-                            note: $note.to_string(),
-                        }),
-                    )*
-                ]
+impl SkillList {
+    /// On a syntax error the table keeps showing the last valid query.
+    fn set_search(&mut self, search: &str) {
+        match Query::parse(search) {
+            Ok(query) => {
+                self.query = Rc::new(query);
+                self.query_error = None;
+            }
+            Err(e) => self.query_error = Some(e),
+        }
+    }
+
+    /// The active search, facets and sort, as noted in exported files.
+    fn filters(&self) -> Vec<(&'static str, String)> {
+        let state = self.url_state();
+        let mut filters = Vec::new();
+        if !state.q.trim().is_empty() {
+            filters.push(("search", state.q));
+        }
+        if !self.tags.is_empty() {
+            let mode = match self.mode {
+                FacetMode::And => "all of",
+                FacetMode::Or => "any of",
             };
+            filters.push(("categories", format!("{} {}", mode, self.tags.join(", "))));
+        }
+        filters.push(("sort", String::from(self.sort.clone())));
+        filters
+    }
+
+    /// The skills that match the search and the facets, in manifest order.
+    fn matching(&self) -> Vec<&Skill> {
+        self.skills
+            .iter()
+            .filter(|s| in_facets(s, &self.tags, self.mode) && self.query.rank(s).is_some())
+            .map(|s| s.as_ref())
+            .collect()
+    }
+
+    /// The sort shown in the table head: the pending one while the rows are hidden.
+    fn shown_sort(&self) -> &Sort {
+        self.sorting.target().unwrap_or(&self.sort)
+    }
+
+    /// The optional columns are shown on request, and always while sorting on one of them,
+    /// in which case the toggle is hidden.
+    fn show_details(&self) -> bool {
+        self.details || self.shown_sort().has_detail()
+    }
+
+    /// The skills in the chosen order, or by relevance while there is a search.
+    fn ranked(&self) -> Vec<&Rc<Skill>> {
+        let mut skills: Vec<&Rc<Skill>> = self.skills.iter().collect();
+        skills.sort_by(|a, b| self.sort.compare(a, b));
+        if !self.query.is_empty() {
+            skills.sort_by_cached_key(|s| {
+                std::cmp::Reverse(self.query.rank(s).map_or(0, |r| r.score))
+            });
+        }
+        skills
+    }
+
+    fn url_query(ctx: &Context<Self>) -> SkillQuery {
+        ctx.link()
+            .location()
+            .and_then(|l| l.query::<SkillQuery>().ok())
+            .unwrap_or_default()
+    }
+
+    fn url_state(&self) -> SkillQuery {
+        SkillQuery {
+            q: self
+                .search_input
+                .cast::<HtmlInputElement>()
+                .map(|i| i.value())
+                .or_else(|| self.initial_search.clone())
+                .unwrap_or_default(),
+            tags: self.tags.join(","),
+            op: self.mode,
+            sort: Some(self.sort.clone()).filter(|s| !s.is_default()),
+            view: self.view,
+        }
+    }
+
+    /// Mirrors the state into the query string. Typing replaces the current history entry,
+    /// while sorting and facets `push` a new one so that back and forward restore them.
+    fn update_url(&self, ctx: &Context<Self>, push: bool) {
+        let (Some(history), Some(route)) = (ctx.link().history(), ctx.link().route::<Route>())
+        else {
+            return;
+        };
+        let query = self.url_state();
+        if query == SkillList::url_query(ctx) {
+            return;
         }
-    skills_vec! {
-        { "Programming", "Rust", 5, "I spent way too much time in Rust" },
-        { "Scripting, DevOps", "Bash", 5, "forever the best scripting language" },
-        { "Virtualization, Bare Metal", "QEMU/KVM", 5, "by far the best hypervizor in the world" },
-        { "Programming, BackEnd", "Rust: Actix", 5, "I still prefer this instead of Poem or Axum" },
-        { "Programming, BackEnd", "Rust: Tonic", 5, "I default to this gor gRPC projects" },
-        { "Programming", "git", 5, "all projects must start with a git repo" },
-        { "Linux Security, Hardware", "Intel SGX", 5, "Check the DeTEE Hacker Challenge" },
-        { "Linux Security, Hardware", "AMD SEV-SNP", 5, "Used to deploy DeTEE VMs" },
-        { "Containers, Packaging", "Docker", 5, "I prefer Podman..." },
-        { "VPN, Networking", "WireGuard", 5, "deployed it to prod for multiple companies" },
-        { "Operating Systems", "GNU/Linux", 5, "Yes, please!" },
-        { "Programming, BackEnd", "gRPC", 5, "not sure if my favorite protocol or not" },
-        { "Programming, BackEnd", "RESTful", 5, "what your API usually ends up looking like" },
-        { "Linux Networking, Security", "firewall, iptables", 5, "I am kinda good at hacking this" },
-        { "Linux Networking", "namespaces, netns", 5, "worked a lot with namespaces" },
-        { "Linux Networking", "tcpdump", 5, "is mandatory for troubleshooting stuff" },
-        { "Linux Security, Cryptography", "GPG", 5, "I use this every day" },
-        { "Linux Security, Cryptography", "OpenSSL", 5, "had to set up a lot of certificates" },
-        { "Linux Security", "OpenSSH", 5, "people should study this a bit more" },
-        { "Web Servers, Load Balancer", "nginx", 5, "my first pick when in need of a web server" },
-        { "DNS Servers", "bind9", 5, "not easy to use; still does the job" },
-        { "DNS Servers", "dnsmasq", 5, "easy to use; great for MITM" },
-        { "DNS Servers", "resolved", 5, "easy to use; does the job" },
-        { "Linux Distribution", "ArchLinux", 5, "my favorite distro; btw, I use arch" },
-        { "Linux Distribution", "Fedora", 5, "probably the best distro in the world" },
-        { "Linux Distribution", "Fedora Silverblue", 5, "best desktop security ever" },
-        { "Linux Distribution", "Fedora CoreOS", 5, "simply amazing for containers" },
-        { "Linux Distribution", "Ubuntu", 5, "not my first pick, but it's really popular" },
-        { "Linux Distribution", "Debian", 5, "I should like this more than Ubuntu, right?" },
-        { "FrontEnd, Web", "HTML, CSS, Bulma", 5, "these technologies are easy to use" },
-        { "Cloud Networking", "AWS", 5, "don't know why I find this easy" },
-        { "Public Cloud, MicroServices", "Kubernetes", 5, "is usually my first pick for prod systems" },
-        { "VPN, Networking", "OpenVPN", 4, "had to work a lot with it cause it's popular" },
-        { "VPN, Networking", "IPsec, StrongSwan", 4, "really hard to master; I prefer WireGuard" },
-        { "Public Cloud", "Digital Ocean", 4, "it's cool when you need something small" },
-        { "Public Cloud", "AWS", 4, "worked with evil corp more than I wanted to" },
-        { "Public Cloud", "GCP", 4, "I almost accepted it as necessary evil" },
-        { "Scripting, DevOps", "Lua", 4, "easy to use but I don't really like it" },
-        { "Web Servers, Load Balancer", "apache2", 4, "have been using it for 10 years" },
-        { "MicroServices, Tracing", "Jaeger", 4, "I created a POC and it looks awesome" },
-        { "Packaging", "dpkg, apt", 4, "worked a lot with Ubuntu" },
-        { "Packaging", "pacman", 4, "I use it a lot; btw I use GCP" },
-        { "Packaging", "rpm, dnf", 4, "I like Fedora a lot" },
-        { "Packaging", "rpm-ostree", 4, "this project is just amazing" },
-        { "Programming, Web, FrontEnd", "Rust: Sycamore", 4, "amazing but check leptos" },
-        { "Programming, Web, FrontEnd", "Rust: libp2p", 4, "I would like to invest more time into it" },
-        { "Programming, BackEnd", "GoLang", 4, "my ex; used to be 5 stars" },
-        { "Linux Networking", "wireshark", 4, "when tcpdump is not enough, get the big gun" },
-        { "Automation, DevOps", "Ansible", 4, "Used to be my main skill some years ago." },
-        { "Linux Security, Cryptography", "dmcrypt", 4, "I worked a lot with encrypted systems" },
-        { "Linux Security", "SELinux", 4, "I used this on Fedora for hardening" },
-        { "Load Balancer", "haproxy", 4, "helped me a lot in multiple situations" },
-        { "Linux Distribution", "RHEL", 4, "not my first pick when it comes to distros" },
-        { "Cloud Networking", "GCP", 4, "I like this more than the AWS" },
-        { "Programming, Web, FrontEnd", "Rust: Yew", 3, "this website runs on Yew via WASM" },
-        { "Programming, Web, FrontEnd", "Rust: Leptos", 3, "the best framework ever made" },
-        { "Smart Contracts, Blockchain", "Solidity, EVM", 3, "not my cup of tea but had to study it" },
-        { "DevOps", "GitLab CI/CD", 3, "had to set up multiple projects" },
-        { "DNS Servers", "PowerDNS", 3, "overcomplicated but sometimes required" },
-        { "EMail Servers", "dovecot, IMAP", 3, "would be cool to work on another project" },
-        { "Linux Distribution", "Gentoo", 3, "had to migrate CyberGhost away from Gentoo" },
-        { "EMail Servers", "postfix, SMTP", 3, "would be cool to work on another project" },
-        { "EMail Servers", "roundcube", 3, "it's easy to set up and does the job" },
-        { "Cloud Networking", "Kubernetes CNI", 3, "I have good understanding of this" },
-        { "Programming, Blockchain", "Rust: ethers/web3", 3, "sadly kinda buggy in comparison to JS" },
-        { "Programming, BackEnd", "GraphQL", 3, "didn't work with this protocol too much" },
-        { "Programming, BackEnd", "PHP", 3, "was my favorite language in 2014" },
-        { "Programming, Web, FrontEnd", "JavaScript", 3, "avoid this like fire; use Rust instead" },
-        { "Programming, Web, FrontEnd", "JS/TS: VueJS", 3, "easy framework but I prefer WASM+Rust" },
-        { "Web Servers, Load Balancer", "Kong", 3, "I tried it out and it looks cool" },
-        { "MicroServices, Service Mesh", "Istio", 3, "I read a lot but never saw it in prod" },
-        { "Virtualization", "VirtualBox", 3, "you should use QEMU/KVM instead" },
-        { "Smart Contracts, Blockchain", "Vyper, EVM", 3, "ecosystem is not as good as Solidity" },
-        { "Smart Contracts, Blockchain", "Rust: Solana", 3, "this where I lost my money" },
-        { "Programming, Blockchain", "Rust: Substrate", 3, "amazing for smart contracts and chains" },
-        { "Virtualization, Bare Metal", "VMware ESXi", 3, "please just use QEMU/KVM instead" },
-        { "Virtualization", "VMware vCenter", 3, "wasted 3 years of my life with this" },
-        { "Scripting, BackEnd", "Python", 3, "who doesn't know Python?" },
-        { "Proxy, MITM", "Squid", 3, "it's garbage; wrote my own proxy/MITM in Go" },
-        { "Programming", "Java", 2, "it makes me sad when I remember I used it" },
-        { "Programming", "C", 2, "it's nice to see what history looks like" },
-        { "Programming", "C++", 2, "had to study it in the University" },
-        { "Programming, MVC", "PHP: Laravel", 2, "overcomplicated; just use microservices" },
-        { "Programming, MVC", "PHP: CakePHP", 2, "didn't touch it in the last decade" },
-        { "Programming, MVC", "Python: Django", 2, "did a few projects in 2016" },
-        { "Packaging", "portage, emerge", 2, "containers can satisfy the same requirement" },
-        { "Linux Distribution", "NixOS", 2, "it's cool, just didn't need it yet" },
-        { "Automation, DevOps", "Terraform", 2, "looks nice but we never really met" },
-        { "Programming, Blockchain", "Go: Cosmos SDK", 2, "looks nice but I prefer substrate." },
-        { "Private Cloud, Bare Metal", "Kubernetes", 2, "would be nice to run it in prod bare-metal" },
-        { "Private Cloud, Bare Metal", "OpenStack", 2, "sadly it's not as popular as it should be" },
-        { "Programming, Web, FrontEnd", "JS/TS: React", 2, "I can confidently read the code" },
-        { "Programming, Web, FrontEnd", "TypeScript", 2, "it's better than JavaScript, I guess..." },
-        { "Cloud Networking", "BGP, EVPN", 2, "did a POC a few years ago" },
-        { "Cloud Networking", "Openstack Neutron", 2, "I should read the docs again" },
-        { "DevOps", "GitHub CI/CD", 2, "Never really got a chance to try it out" },
-        { "FrontEnd, Web", "Bootstrap", 2, "I prefer Bulma" },
-        { "Linux Networking, Security", "firewall, ebtables", 2, "I should migrate away from iptables" },
-        { "FrontEnd, Web", "Tailwind", 2, "I should invest more time into this" },
-        { "Linux Security", "AppArmor", 2, "I prefer SELinux" },
-        { "Operating Systems", "FreeBSD", 2, "looks awesome but never had the opportunity" },
-        { "Operating Systems", "Mac", 2, "it's cool, I guess..." },
-        { "Operating Systems", "Windows", 1, "No. Just no." },
-        { "Public Cloud", "Azure", 1, "No. Just no." },
-        { "Virtualization, Bare Metal", "HyperV", 1, "No. Just no." },
+        let updated = match push {
+            true => history.push_with_query(route, query),
+            false => history.replace_with_query(route, query),
+        };
+        if let Err(e) = updated {
+            log::error!("could not update the URL: {}", e);
+        }
+    }
+}
+
+fn split_tags(tags: &str) -> Vec<String> {
+    tags.split(',')
+        .filter(|t| !t.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+fn skills_vector(portfolio: &Portfolio, locale: Locale) -> Rc<Vec<Rc<Skill>>> {
+    Rc::new(
+        portfolio
+            .localized(locale.code())
+            .skills
+            .iter()
+            .map(|s| Rc::new(s.clone()))
+            .collect(),
+    )
+}
+
+/// Jobs and projects by the technologies they list.
+fn usage_map(portfolio: &Portfolio, locale: Locale) -> HashMap<String, Rc<Vec<Usage>>> {
+    let portfolio = portfolio.localized(locale.code());
+    let mut usage: HashMap<String, Vec<Usage>> = HashMap::new();
+    for job in &portfolio.jobs {
+        for technology in &job.skills {
+            usage.entry(technology.clone()).or_default().push(Usage {
+                label: format!("{}, {}", job.title, job.company),
+                route: Route::CV.localized(locale),
+                query: Some(CvQuery::job(&job.id)),
+            });
+        }
+    }
+    for project in &portfolio.projects {
+        for technology in &project.skills {
+            usage.entry(technology.clone()).or_default().push(Usage {
+                label: project.title.clone(),
+                route: Route::ProjectDetail {
+                    id: project.id.clone(),
+                }
+                .localized(locale),
+                query: None,
+            });
+        }
+    }
+    usage
+        .into_iter()
+        .map(|(technology, used_in)| (technology, Rc::new(used_in)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::manifest::Level;
+
+    fn toggled(sort: &str, column: Column, add: bool) -> String {
+        Sort::from(sort.to_string()).toggled(column, add).into()
+    }
+
+    fn skill(technology: &str, level: usize, years: Option<u8>) -> Skill {
+        Skill {
+            categories: vec!["Programming".to_string()],
+            technology: technology.to_string(),
+            level: Level(level),
+            note: String::new(),
+            years,
+            last_used: None,
+            trend: None,
+            translations: Default::default(),
+        }
+    }
+
+    fn sorted(sort: &str, skills: &[Skill]) -> Vec<String> {
+        let sort = Sort::from(sort.to_string());
+        let mut skills: Vec<&Skill> = skills.iter().collect();
+        skills.sort_by(|a, b| sort.compare(a, b));
+        skills.iter().map(|s| s.technology.clone()).collect()
+    }
+
+    #[test]
+    fn click_cycles_one_key() {
+        assert_eq!(toggled("-level", Column::Tech, false), "tech");
+        assert_eq!(toggled("tech", Column::Tech, false), "-tech");
+        assert_eq!(toggled("-tech", Column::Tech, false), "none");
+        assert_eq!(toggled("none", Column::Tech, false), "tech");
+        // A click on one of several keys starts over with that key alone.
+        assert_eq!(toggled("-level,tech", Column::Level, false), "level");
+        assert_eq!(toggled("-level,tech", Column::Years, false), "years");
+    }
+
+    #[test]
+    fn shift_click_cycles_a_key_among_the_others() {
+        assert_eq!(toggled("-level", Column::Tech, true), "-level,tech");
+        assert_eq!(toggled("-level,tech", Column::Tech, true), "-level,-tech");
+        assert_eq!(toggled("-level,-tech", Column::Tech, true), "-level");
+        assert_eq!(toggled("-level,tech", Column::Level, true), "tech");
+        assert_eq!(toggled("none", Column::Years, true), "years");
+    }
+
+    #[test]
+    fn parses_and_writes_keys() {
+        let sort = |s: &str| String::from(Sort::from(s.to_string()));
+        assert_eq!(sort("-level,tech"), "-level,tech");
+        assert_eq!(sort("-level,bogus,used"), "-level,used");
+        assert_eq!(sort("none"), "none");
+        assert_eq!(sort(""), "none");
+        assert!(Sort::from("-level".to_string()).is_default());
+    }
+
+    #[test]
+    fn sorts_by_every_key_in_order() {
+        let skills = [
+            skill("rust", 4, Some(3)),
+            skill("Go", 5, None),
+            skill("C", 4, Some(8)),
+            skill("zig", 4, None),
+        ];
+        assert_eq!(sorted("-level,tech", &skills), ["Go", "C", "rust", "zig"]);
+        assert_eq!(sorted("level,-tech", &skills), ["zig", "rust", "C", "Go"]);
+        // Equal skills keep the manifest order.
+        assert_eq!(sorted("level", &skills), ["rust", "C", "zig", "Go"]);
+        assert_eq!(sorted("none", &skills), ["rust", "Go", "C", "zig"]);
+    }
+
+    #[test]
+    fn missing_values_sort_last_both_ways() {
+        let skills = [
+            skill("Go", 5, None),
+            skill("rust", 4, Some(3)),
+            skill("C", 4, Some(8)),
+        ];
+        assert_eq!(sorted("years", &skills), ["rust", "C", "Go"]);
+        assert_eq!(sorted("-years", &skills), ["C", "rust", "Go"]);
     }
 }
@@ -0,0 +1,119 @@
+use crate::i18n::Locale;
+use crate::manifest::{Education, JobMeta, YearMonth};
+use yew::prelude::*;
+
+const WIDTH: f64 = 1000.0;
+const MARGIN: f64 = 20.0;
+const LANE: f64 = 30.0;
+const BAR: f64 = 22.0;
+const AXIS: f64 = 24.0;
+
+/// Renders jobs as horizontal bars on a shared time axis, with overlapping jobs on
+/// separate lanes and graduations as markers on the last lane.
+/// `select` receives the id of the job whose bar was clicked.
+pub fn render(
+    jobs: &[&JobMeta],
+    education: &[Education],
+    locale: Locale,
+    select: Callback<String>,
+) -> Html {
+    let today = YearMonth::today();
+    let first_year = jobs
+        .iter()
+        .map(|j| j.start.year)
+        .chain(education.iter().map(|e| e.graduation))
+        .min()
+        .unwrap_or(today.year);
+    let years = (first_year..=today.year + 1).collect::<Vec<_>>();
+    let months = (years.len() as f64 - 1.0) * 12.0;
+    let x = |date: YearMonth| {
+        let offset = (date.year - first_year) as f64 * 12.0 + date.month as f64 - 1.0;
+        MARGIN + offset / months * (WIDTH - 2.0 * MARGIN)
+    };
+
+    let lanes = lanes(jobs);
+    let job_lanes = lanes.iter().copied().max().map_or(0, |l| l + 1);
+    let education_lane = job_lanes as f64 * LANE;
+    let height = match education.is_empty() {
+        true => education_lane + AXIS,
+        false => education_lane + LANE + AXIS,
+    };
+    let axis = height - AXIS;
+
+    let bar = |(job, lane): (&&JobMeta, &usize)| {
+        let start = x(job.start);
+        let width = (x(job.end.unwrap_or(today).next()) - start).max(4.0);
+        let y = *lane as f64 * LANE;
+        let end = match job.end {
+            Some(end) => end.to_string(),
+            None => locale.t("job.present"),
+        };
+        let id = job.id.clone();
+        let select = select.clone();
+        html! {
+            <g class="timeline-bar" onclick={Callback::from(move |_| select.emit(id.clone()))}>
+                <title>{format!("{}, {}: {} - {}", job.title, job.company, job.start, end)}</title>
+                <rect x={start.to_string()} y={y.to_string()} rx="6"
+                    width={width.to_string()} height={BAR.to_string()}/>
+                if width > job.company.chars().count() as f64 * 7.0 + 12.0 {
+                    <text x={(start + 8.0).to_string()} y={(y + 15.0).to_string()}>
+                        {&job.company}
+                    </text>
+                }
+            </g>
+        }
+    };
+
+    html! {
+        <svg class="timeline" viewBox={format!("0 0 {} {}", WIDTH, height)}
+            role="img" aria-label={locale.t("cv.timeline")}>
+            { for years.iter().map(|year| {
+                let tick = x(YearMonth { year: *year, month: 1 });
+                html! {
+                    <g class="timeline-axis">
+                        <line x1={tick.to_string()} x2={tick.to_string()}
+                            y1="0" y2={(axis + 4.0).to_string()}/>
+                        <text x={tick.to_string()} y={(axis + 18.0).to_string()}>{year}</text>
+                    </g>
+                }
+            }) }
+            { for jobs.iter().zip(lanes.iter()).map(bar) }
+            { for education.iter().map(|e| {
+                let cx = x(YearMonth { year: e.graduation, month: 7 });
+                let cy = education_lane + BAR / 2.0;
+                html! {
+                    <g class="timeline-education">
+                        <title>{format!("{}, {}: {}", e.title, e.institution, e.graduation)}</title>
+                        <circle cx={cx.to_string()} cy={cy.to_string()} r="6"/>
+                    </g>
+                }
+            }) }
+        </svg>
+    }
+}
+
+/// Puts every job on the first lane that is free when it starts, oldest jobs first.
+fn lanes(jobs: &[&JobMeta]) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..jobs.len()).collect();
+    order.sort_by_key(|i| jobs[*i].start);
+    let mut lane_ends: Vec<Option<YearMonth>> = Vec::new();
+    let mut lanes = vec![0; jobs.len()];
+    for i in order {
+        let job = jobs[i];
+        // Current jobs (no end) keep their lane for good.
+        match lane_ends
+            .iter()
+            .position(|end| end.is_some_and(|e| e < job.start))
+        {
+            Some(lane) => {
+                lane_ends[lane] = job.end;
+                lanes[i] = lane;
+            }
+            None => {
+                lanes[i] = lane_ends.len();
+                lane_ends.push(job.end);
+            }
+        }
+    }
+    lanes
+}
@@ -0,0 +1,116 @@
+use gloo_timers::callback::Timeout;
+use yew::html::Scope;
+use yew::Component;
+
+/// The fade-out of `.skill-hidden` in index.css, played by the table rows before a new sort.
+pub const SKILL_ROW_EXIT_MS: u32 = 500;
+/// The fade-out of `.jobtext-hidden` in index.css, played before another job text is shown.
+pub const JOB_TEXT_EXIT_MS: u32 = 1000;
+
+/// Identifies one exit animation, so that the timer of a superseded one is ignored.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Ticket(u64);
+
+/// Sequences an exit animation and the state change behind it: `start` hides the
+/// current content while the exit plays, `finish` hands back the new state once it
+/// is over, after which the content enters with its own CSS transition.
+/// Starting again while exiting retargets the transition and restarts the wait, so
+/// only the last input of a burst of clicks is applied.
+pub struct Transition<T> {
+    target: Option<T>,
+    generation: u64,
+    timer: Option<Timeout>,
+}
+
+impl<T> Default for Transition<T> {
+    fn default() -> Self {
+        Transition {
+            target: None,
+            generation: 0,
+            timer: None,
+        }
+    }
+}
+
+impl<T> Transition<T> {
+    /// Begins leaving the current state for `target`, superseding any transition in flight.
+    pub fn start(&mut self, target: T) -> Ticket {
+        self.generation += 1;
+        self.target = Some(target);
+        self.timer = None;
+        Ticket(self.generation)
+    }
+
+    /// The new state, if `ticket` belongs to the transition in flight.
+    pub fn finish(&mut self, ticket: Ticket) -> Option<T> {
+        if ticket != Ticket(self.generation) {
+            return None;
+        }
+        self.timer = None;
+        self.target.take()
+    }
+
+    /// Abandons the transition in flight; its timer is dropped and its ticket goes stale.
+    pub fn cancel(&mut self) {
+        self.generation += 1;
+        self.target = None;
+        self.timer = None;
+    }
+
+    pub fn is_exiting(&self) -> bool {
+        self.target.is_some()
+    }
+
+    pub fn target(&self) -> Option<&T> {
+        self.target.as_ref()
+    }
+
+    /// Starts a transition and sends `done` with its ticket to the component after `ms`.
+    /// Replacing the previous timer cancels it.
+    pub fn start_after<C: Component>(
+        &mut self,
+        target: T,
+        ms: u32,
+        link: &Scope<C>,
+        done: fn(Ticket) -> C::Message,
+    ) {
+        let ticket = self.start(target);
+        let link = link.clone();
+        self.timer = Some(Timeout::new(ms, move || link.send_message(done(ticket))));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finishes_with_the_target() {
+        let mut transition = Transition::default();
+        let ticket = transition.start("tech");
+        assert!(transition.is_exiting());
+        assert_eq!(transition.finish(ticket), Some("tech"));
+        assert!(!transition.is_exiting());
+        assert_eq!(transition.finish(ticket), None);
+    }
+
+    #[test]
+    fn a_new_start_makes_earlier_tickets_stale() {
+        let mut transition = Transition::default();
+        let first = transition.start("story");
+        let second = transition.start("tech");
+        assert_eq!(transition.finish(first), None);
+        assert!(transition.is_exiting());
+        assert_eq!(transition.target(), Some(&"tech"));
+        assert_eq!(transition.finish(second), Some("tech"));
+    }
+
+    #[test]
+    fn cancel_drops_the_transition() {
+        let mut transition = Transition::default();
+        let ticket = transition.start(1);
+        transition.cancel();
+        assert!(!transition.is_exiting());
+        assert_eq!(transition.finish(ticket), None);
+    }
+}
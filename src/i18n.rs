@@ -0,0 +1,167 @@
+use crate::manifest::LOCALES;
+use crate::Route;
+use std::collections::HashMap;
+use yew::prelude::*;
+use yew_router::history::{BrowserHistory, History, HistoryListener, Location};
+use yew_router::prelude::*;
+
+static LOCALES_JSON: &str = include_str!(concat!(env!("OUT_DIR"), "/locales.json"));
+const STORAGE_KEY: &str = "locale";
+
+thread_local! {
+    static BUNDLES: HashMap<String, HashMap<String, String>> =
+        serde_json::from_str(LOCALES_JSON).expect("locales.json is generated by build.rs");
+}
+
+/// One of the languages in [`LOCALES`]; the default is the first one.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Locale(&'static str);
+
+impl Default for Locale {
+    fn default() -> Self {
+        Locale(LOCALES[0])
+    }
+}
+
+impl Locale {
+    pub fn all() -> impl Iterator<Item = Locale> {
+        LOCALES.iter().map(|code| Locale(code))
+    }
+
+    pub fn parse(code: &str) -> Option<Locale> {
+        LOCALES
+            .iter()
+            .find(|c| **c == code)
+            .map(|code| Locale(code))
+    }
+
+    pub fn code(&self) -> &'static str {
+        self.0
+    }
+
+    pub fn is_default(&self) -> bool {
+        *self == Locale::default()
+    }
+
+    /// Looks up a UI string such as `nav.home`, falling back to the default language.
+    pub fn t(&self, key: &str) -> String {
+        BUNDLES.with(|bundles| {
+            [self.0, LOCALES[0]]
+                .iter()
+                .find_map(|code| bundles.get(*code).and_then(|b| b.get(key)))
+                .cloned()
+                .unwrap_or_else(|| {
+                    log::warn!("missing UI string {}", key);
+                    key.to_string()
+                })
+        })
+    }
+
+    /// Formats a number of months like "3 yrs 10 mos".
+    pub fn duration(&self, months: u32) -> String {
+        let unit = |n: u32, one: &str, many: &str| match n {
+            1 => format!("1 {}", self.t(one)),
+            n => format!("{} {}", n, self.t(many)),
+        };
+        match (months / 12, months % 12) {
+            (0, m) => unit(m, "duration.month", "duration.months"),
+            (y, 0) => unit(y, "duration.year", "duration.years"),
+            (y, m) => format!(
+                "{} {}",
+                unit(y, "duration.year", "duration.years"),
+                unit(m, "duration.month", "duration.months")
+            ),
+        }
+    }
+}
+
+fn stored() -> Option<Locale> {
+    let storage = web_sys::window()?.local_storage().ok()??;
+    Locale::parse(&storage.get_item(STORAGE_KEY).ok()??)
+}
+
+fn store(locale: Locale) {
+    if let Some(Ok(Some(storage))) = web_sys::window().map(|w| w.local_storage()) {
+        let _ = storage.set_item(STORAGE_KEY, locale.code());
+    }
+}
+
+/// The route the app starts on: the current URL, moved to the persisted language
+/// when the URL itself does not name one.
+pub fn initial_route() -> Route {
+    let path = web_sys::window()
+        .and_then(|w| w.location().pathname().ok())
+        .unwrap_or_default();
+    let route = Route::recognize(&path).unwrap_or(Route::Home);
+    match (route.lang(), stored()) {
+        (None, Some(locale)) => route.localized(locale),
+        _ => route,
+    }
+}
+
+/// The parameters of a query string, in order.
+pub type Query = Vec<(String, String)>;
+
+/// The query string of the current URL, which is kept when switching languages.
+pub fn current_query() -> Query {
+    BrowserHistory::new()
+        .location()
+        .query::<Query>()
+        .unwrap_or_default()
+}
+
+#[derive(Properties, PartialEq)]
+pub struct LocaleSyncProps {
+    pub onchange: Callback<Route>,
+}
+
+/// Keeps the URL, the persisted language and the app in sync. Must be rendered inside
+/// the router: it reports every route change and remembers the language of the last one.
+pub struct LocaleSync {
+    _listener: Option<HistoryListener>,
+}
+
+impl Component for LocaleSync {
+    type Message = ();
+    type Properties = LocaleSyncProps;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let history = ctx.link().history();
+        let listener = history.as_ref().map(|h| {
+            let link = ctx.link().clone();
+            h.listen(move || link.send_message(()))
+        });
+        let initial = initial_route();
+        match history {
+            Some(history) if ctx.link().route::<Route>().as_ref() != Some(&initial) => {
+                let query = current_query();
+                if query.is_empty() {
+                    history.replace(initial)
+                } else if let Err(e) = history.replace_with_query(initial, query) {
+                    log::error!("could not keep the query string: {}", e);
+                }
+            }
+            _ => ctx.link().send_message(()),
+        }
+        Self {
+            _listener: listener,
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, _msg: Self::Message) -> bool {
+        if let Some(route) = ctx.link().location().and_then(|l| l.route::<Route>()) {
+            store(route.locale());
+            ctx.props().onchange.emit(route);
+        }
+        false
+    }
+
+    fn view(&self, _ctx: &Context<Self>) -> Html {
+        html! {}
+    }
+}
+
+#[derive(Properties, PartialEq)]
+pub struct LocaleProps {
+    pub locale: Locale,
+}
@@ -1,5 +1,8 @@
 mod pages;
 mod components;
+mod manifest;
+mod i18n;
+use i18n::{Locale, LocaleSync};
 use yew::prelude::*;
 use yew_router::prelude::*;
 use yew::html::Scope;
@@ -14,15 +17,76 @@ pub enum Route {
     CV,
     #[at("/projects")]
     Projects,
+    #[at("/projects/:id")]
+    ProjectDetail { id: String },
     #[at("/contact")]
     Contact,
+    #[at("/:lang")]
+    LocalizedHome { lang: String },
+    #[at("/:lang/skills")]
+    LocalizedSkills { lang: String },
+    #[at("/:lang/cv")]
+    LocalizedCV { lang: String },
+    #[at("/:lang/projects")]
+    LocalizedProjects { lang: String },
+    #[at("/:lang/projects/:id")]
+    LocalizedProjectDetail { lang: String, id: String },
+    #[at("/:lang/contact")]
+    LocalizedContact { lang: String },
+}
+
+impl Route {
+    pub fn lang(&self) -> Option<&str> {
+        match self {
+            Route::LocalizedHome { lang }
+            | Route::LocalizedSkills { lang }
+            | Route::LocalizedCV { lang }
+            | Route::LocalizedProjects { lang }
+            | Route::LocalizedProjectDetail { lang, .. }
+            | Route::LocalizedContact { lang } => Some(lang),
+            _ => None,
+        }
+    }
+
+    /// Unknown language prefixes fall back to the default locale.
+    pub fn locale(&self) -> Locale {
+        self.lang().and_then(Locale::parse).unwrap_or_default()
+    }
+
+    /// The same page in another language; the default one has no prefix.
+    pub fn localized(&self, locale: Locale) -> Route {
+        let page = match self {
+            Route::LocalizedHome { .. } => Route::Home,
+            Route::LocalizedSkills { .. } => Route::Skills,
+            Route::LocalizedCV { .. } => Route::CV,
+            Route::LocalizedProjects { .. } => Route::Projects,
+            Route::LocalizedProjectDetail { id, .. } => Route::ProjectDetail { id: id.clone() },
+            Route::LocalizedContact { .. } => Route::Contact,
+            page => page.clone(),
+        };
+        if locale.is_default() {
+            return page;
+        }
+        let lang = locale.code().to_string();
+        match page {
+            Route::Home => Route::LocalizedHome { lang },
+            Route::Skills => Route::LocalizedSkills { lang },
+            Route::CV => Route::LocalizedCV { lang },
+            Route::Projects => Route::LocalizedProjects { lang },
+            Route::ProjectDetail { id } => Route::LocalizedProjectDetail { lang, id },
+            Route::Contact => Route::LocalizedContact { lang },
+            localized => localized,
+        }
+    }
 }
 
 pub enum Msg {
     ToggleNavbar,
+    SetRoute(Route),
 }
 pub struct Model {
     navbar_active: bool,
+    route: Route,
 }
 
 impl Component for Model {
@@ -32,6 +96,7 @@ impl Component for Model {
     fn create(_ctx: &Context<Self>) -> Self {
         Self {
             navbar_active: false,
+            route: i18n::initial_route(),
         }
     }
 
@@ -41,12 +106,17 @@ impl Component for Model {
                 self.navbar_active = !self.navbar_active;
                 true
             }
+            Msg::SetRoute(route) => {
+                self.route = route;
+                true
+            }
         }
     }
 
     fn view(&self, ctx: &Context<Self>) -> Html {
         html! {
             <BrowserRouter>
+                <LocaleSync onchange={ctx.link().callback(Msg::SetRoute)}/>
                 { self.view_nav(ctx.link()) }
                 <main style="margin-top: 81px; width: 100vw">
                     <Switch<Route> render={Switch::render(switch)} />
@@ -58,7 +128,9 @@ impl Component for Model {
 
 impl Model {
     fn view_nav(&self, link: &Scope<Self>) -> Html {
-        let Self { navbar_active, .. } = *self;
+        let navbar_active = self.navbar_active;
+        let locale = self.route.locale();
+        let query = i18n::current_query();
 
         let active_class = if navbar_active { "is-active" } else { "" };
 
@@ -79,21 +151,31 @@ impl Model {
                 <div class={classes!("navbar-menu", active_class)}
                         onclick={link.callback(|_| Msg::ToggleNavbar)}>
                     <div class="navbar-end">
-                        <Link<Route> classes={classes!("navbar-item")} to={Route::Home}>
-                            { "home" }
+                        <Link<Route> classes={classes!("navbar-item")} to={Route::Home.localized(locale)}>
+                            { locale.t("nav.home") }
                         </Link<Route>>
-                        <Link<Route> classes={classes!("navbar-item")} to={Route::Skills}>
-                            { "skills" }
+                        <Link<Route> classes={classes!("navbar-item")} to={Route::Skills.localized(locale)}>
+                            { locale.t("nav.skills") }
                         </Link<Route>>
-                        <Link<Route> classes={classes!("navbar-item")} to={Route::CV}>
-                            { "cv" }
+                        <Link<Route> classes={classes!("navbar-item")} to={Route::CV.localized(locale)}>
+                            { locale.t("nav.cv") }
                         </Link<Route>>
-                        <Link<Route> classes={classes!("navbar-item")} to={Route::Projects}>
-                            { "projects" }
+                        <Link<Route> classes={classes!("navbar-item")} to={Route::Projects.localized(locale)}>
+                            { locale.t("nav.projects") }
                         </Link<Route>>
-                        <Link<Route> classes={classes!("navbar-item")} to={Route::Contact}>
-                            { "contact" }
+                        <Link<Route> classes={classes!("navbar-item")} to={Route::Contact.localized(locale)}>
+                            { locale.t("nav.contact") }
                         </Link<Route>>
+                        <div class="navbar-item" aria-label={locale.t("nav.language")}>
+                            { for Locale::all().map(|l| html! {
+                                <Link<Route, i18n::Query> classes={classes!("langlink",
+                                        (l == locale).then_some("is-active"))}
+                                    to={self.route.localized(l)}
+                                    query={(!query.is_empty()).then(|| query.clone())}>
+                                    { l.code() }
+                                </Link<Route, i18n::Query>>
+                            }) }
+                        </div>
                     </div>
                 </div>
             </nav>
@@ -102,12 +184,14 @@ impl Model {
 }
 
 fn switch(routes: &Route) -> Html {
-    match routes.clone() {
-        Route::Home => html! { <pages::home::Home/> },
-        Route::Skills => html! { <pages::skills::Skills/> },
-        Route::CV => html! { <pages::cv::CV/> },
-        Route::Projects => html! { <pages::projects::Projects /> },
-        Route::Contact => html! { <pages::contact::Contact /> },
+    let locale = routes.locale();
+    match routes.localized(Locale::default()) {
+        Route::Skills => html! { <pages::skills::Skills {locale}/> },
+        Route::CV => html! { <pages::cv::CV {locale}/> },
+        Route::Projects => html! { <pages::projects::Projects {locale}/> },
+        Route::ProjectDetail { id } => html! { <pages::project::ProjectDetail {locale} {id}/> },
+        Route::Contact => html! { <pages::contact::Contact {locale}/> },
+        _ => html! { <pages::home::Home {locale}/> },
     }
 }
 
@@ -0,0 +1,504 @@
+// Conversion between the portfolio manifest and https://jsonresume.org/schema.
+// Like schema.rs this only depends on std and serde, so tools can include it with #[path].
+use super::{
+    Arrangement, Contact, ContactDelivery, Cv, Education, Employment, Issue, JobMeta, Level,
+    MediaLink, Portfolio, ProjectMeta, Skill, YearMonth,
+};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Resume {
+    #[serde(default)]
+    pub basics: Basics,
+    #[serde(default)]
+    pub work: Vec<Work>,
+    #[serde(default)]
+    pub education: Vec<Study>,
+    #[serde(default)]
+    pub skills: Vec<ResumeSkill>,
+    #[serde(default)]
+    pub projects: Vec<ResumeProject>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Basics {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+    #[serde(default)]
+    pub profiles: Vec<Profile>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Profile {
+    pub network: String,
+    #[serde(default)]
+    pub url: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Work {
+    pub name: String,
+    #[serde(default)]
+    pub position: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub location: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub start_date: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub end_date: Option<String>,
+    #[serde(default)]
+    pub summary: String,
+    #[serde(default)]
+    pub highlights: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Study {
+    pub institution: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub area: Option<String>,
+    #[serde(default)]
+    pub study_type: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub end_date: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResumeSkill {
+    pub name: String,
+    #[serde(default)]
+    pub level: String,
+    #[serde(default)]
+    pub keywords: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResumeProject {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub start_date: Option<String>,
+    #[serde(default)]
+    pub highlights: Vec<String>,
+    #[serde(default)]
+    pub keywords: Vec<String>,
+}
+
+const LEVELS: [&str; 5] = ["Novice", "Beginner", "Intermediate", "Advanced", "Expert"];
+
+impl Resume {
+    /// Dates the manifest cannot take: every job needs a start date, and dates have to be
+    /// like "2022-08-01", "2022-08" or "2022". Paths use the names of the resume.
+    // Only the import_resume example checks them; the app never imports resumes.
+    #[allow(dead_code)]
+    pub fn date_issues(&self) -> Vec<Issue> {
+        let mut issues = Vec::new();
+        let mut check = |date: &Option<String>, required: bool, path: String| {
+            let message = match date {
+                None if required => "missing date",
+                Some(d) if from_iso_date(d).is_none() => {
+                    "invalid date, expected YYYY-MM-DD, YYYY-MM or YYYY"
+                }
+                _ => return,
+            };
+            issues.push(Issue {
+                path,
+                message: message.to_string(),
+            });
+        };
+        for (i, w) in self.work.iter().enumerate() {
+            check(&w.start_date, true, format!("work[{}].startDate", i));
+            check(&w.end_date, false, format!("work[{}].endDate", i));
+        }
+        for (i, p) in self.projects.iter().enumerate() {
+            check(&p.start_date, false, format!("projects[{}].startDate", i));
+        }
+        for (i, e) in self.education.iter().enumerate() {
+            check(&e.end_date, false, format!("education[{}].endDate", i));
+        }
+        issues
+    }
+}
+
+impl From<&Portfolio> for Resume {
+    fn from(p: &Portfolio) -> Self {
+        Resume {
+            basics: Basics {
+                name: None,
+                // The manifest keeps the address away from scrapers.
+                email: None,
+                profiles: p
+                    .contact
+                    .media
+                    .iter()
+                    .map(|m| Profile {
+                        network: m.label.clone(),
+                        url: m.url.clone(),
+                    })
+                    .collect(),
+            },
+            work: p
+                .jobs
+                .iter()
+                .map(|j| Work {
+                    name: j.company.clone(),
+                    position: j.title.clone(),
+                    location: Some(j.location.clone()),
+                    start_date: Some(to_iso_date(j.start)),
+                    end_date: j.end.map(to_iso_date),
+                    summary: j.story.trim().to_string(),
+                    highlights: list_items(&j.resp),
+                })
+                .collect(),
+            education: p
+                .education
+                .iter()
+                .map(|e| Study {
+                    institution: e.institution.clone(),
+                    area: None,
+                    study_type: e.title.clone(),
+                    end_date: Some(e.graduation.to_string()),
+                })
+                .collect(),
+            skills: p
+                .skills
+                .iter()
+                .map(|s| ResumeSkill {
+                    name: s.technology.clone(),
+                    level: LEVELS[s.level.0.clamp(1, 5) - 1].to_string(),
+                    keywords: s.categories.clone(),
+                })
+                .collect(),
+            projects: p
+                .projects
+                .iter()
+                .map(|p| ResumeProject {
+                    name: p.title.clone(),
+                    description: p.description.clone(),
+                    url: Some(p.demo.clone().unwrap_or_else(|| p.source.clone())),
+                    start_date: p.year.map(|y| y.to_string()),
+                    highlights: p.features.clone(),
+                    keywords: p.skills.clone(),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Builds a manifest from a resume. JSON Resume has no equivalent for some required
+/// fields (contact schedule, project images...), so they are left empty and the
+/// build points them out once the result is saved as `portfolio.toml`. Jobs are
+/// imported as full-time and on-site. Dates that cannot be read are left out, except for
+/// the required start of a job, so check [`Resume::date_issues`] first.
+impl From<Resume> for Portfolio {
+    fn from(r: Resume) -> Self {
+        Portfolio {
+            contact: Contact {
+                schedule: String::new(),
+                schedule_note: String::new(),
+                email_img: String::new(),
+                email_note: String::new(),
+                media: r
+                    .basics
+                    .profiles
+                    .into_iter()
+                    .map(|p| MediaLink {
+                        label: p.network,
+                        url: p.url,
+                    })
+                    .collect(),
+                form: r
+                    .basics
+                    .email
+                    .map(|email| ContactDelivery::Mailto { email }),
+            },
+            cv: Cv { pdf: String::new() },
+            skills: r
+                .skills
+                .into_iter()
+                .map(|s| Skill {
+                    categories: s.keywords,
+                    technology: s.name,
+                    level: from_resume_level(&s.level),
+                    note: String::new(),
+                    years: None,
+                    last_used: None,
+                    trend: None,
+                    translations: Default::default(),
+                })
+                .collect(),
+            jobs: r
+                .work
+                .into_iter()
+                .map(|w| JobMeta {
+                    id: slug(&w.name),
+                    title: w.position,
+                    company: w.name,
+                    location: w.location.unwrap_or_default(),
+                    arrangement: Arrangement::OnSite,
+                    employment: Employment::FullTime,
+                    skills: Vec::new(),
+                    start: w
+                        .start_date
+                        .as_deref()
+                        .and_then(from_iso_date)
+                        .unwrap_or_default(),
+                    end: w.end_date.as_deref().and_then(from_iso_date),
+                    story: w.summary,
+                    tech: String::new(),
+                    resp: w.highlights.iter().map(|h| format!("- {}\n", h)).collect(),
+                    translations: Default::default(),
+                })
+                .collect(),
+            projects: r
+                .projects
+                .into_iter()
+                .map(|p| ProjectMeta {
+                    id: slug(&p.name),
+                    title: p.name,
+                    description: p.description,
+                    demo: None,
+                    source: p.url.unwrap_or_default(),
+                    img: String::new(),
+                    skills: p.keywords,
+                    year: p.start_date.and_then(|d| from_iso_date(&d)).map(|d| d.year),
+                    status: None,
+                    license: None,
+                    details: String::new(),
+                    features: p.highlights,
+                    screenshots: Vec::new(),
+                    links: Vec::new(),
+                    translations: Default::default(),
+                })
+                .collect(),
+            education: r
+                .education
+                .into_iter()
+                .map(|e| Education {
+                    title: match e.area {
+                        Some(area) if !e.study_type.is_empty() => {
+                            format!("{} in {}", e.study_type, area)
+                        }
+                        Some(area) => area,
+                        None => e.study_type,
+                    },
+                    institution: e.institution,
+                    location: String::new(),
+                    graduation: e
+                        .end_date
+                        .and_then(|d| from_iso_date(&d))
+                        .map_or(0, |d| d.year),
+                })
+                .collect(),
+        }
+    }
+}
+
+fn to_iso_date(date: YearMonth) -> String {
+    format!("{}-{:02}", date.year, date.month)
+}
+
+/// "2022-08-01" and "2022-08" -> 08/2022; a bare year is taken as January.
+fn from_iso_date(date: &str) -> Option<YearMonth> {
+    let mut parts = date.split('-');
+    let year = parts.next()?;
+    let month = parts.next().unwrap_or("1");
+    YearMonth::try_from(format!("{}/{}", month, year)).ok()
+}
+
+fn from_resume_level(level: &str) -> Level {
+    let level = level.trim();
+    if let Some(i) = LEVELS.iter().position(|l| l.eq_ignore_ascii_case(level)) {
+        return Level(i + 1);
+    }
+    if level.eq_ignore_ascii_case("master") {
+        return Level(5);
+    }
+    Level(level.parse().unwrap_or(3))
+}
+
+fn slug(name: &str) -> String {
+    name.to_lowercase()
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+/// Top-level Markdown list items, which is what the job texts use for highlights.
+fn list_items(markdown: &str) -> Vec<String> {
+    markdown
+        .lines()
+        .filter_map(|l| l.strip_prefix("- ").or_else(|| l.strip_prefix("* ")))
+        .map(|l| l.trim().to_string())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn manifest() -> Portfolio {
+        toml::from_str(include_str!("../../portfolio.toml")).unwrap()
+    }
+
+    /// Through JSON, like the exported file.
+    fn export(portfolio: &Portfolio) -> Resume {
+        let json = serde_json::to_string(&Resume::from(portfolio)).unwrap();
+        serde_json::from_str(&json).unwrap()
+    }
+
+    #[test]
+    fn reads_iso_dates() {
+        let date = |year, month| Some(YearMonth { year, month });
+        assert_eq!(from_iso_date("2022-08-01"), date(2022, 8));
+        assert_eq!(from_iso_date("2022-08"), date(2022, 8));
+        assert_eq!(from_iso_date("2022"), date(2022, 1));
+        assert_eq!(from_iso_date("2020-13"), None);
+        assert_eq!(from_iso_date("2020-00"), None);
+        assert_eq!(from_iso_date("20-01"), None);
+        assert_eq!(from_iso_date("August 2022"), None);
+        assert_eq!(from_iso_date(""), None);
+    }
+
+    #[test]
+    fn reports_missing_and_invalid_dates() {
+        let resume: Resume = serde_json::from_str(
+            r#"{
+                "work": [
+                    {"name": "A", "startDate": "2020-13"},
+                    {"name": "B"},
+                    {"name": "C", "startDate": "2019-02", "endDate": "soon"}
+                ],
+                "projects": [{"name": "P", "startDate": "2021"}],
+                "education": [{"institution": "U", "endDate": "2015-07-01"}]
+            }"#,
+        )
+        .unwrap();
+        let paths: Vec<String> = resume.date_issues().into_iter().map(|i| i.path).collect();
+        assert_eq!(
+            paths,
+            ["work[0].startDate", "work[1].startDate", "work[2].endDate"]
+        );
+    }
+
+    #[test]
+    fn the_manifest_survives_an_export_and_import() {
+        let portfolio = manifest();
+        let resume = export(&portfolio);
+        assert!(resume.date_issues().is_empty());
+        let imported = Portfolio::from(resume);
+
+        assert_eq!(imported.contact.media, portfolio.contact.media);
+        assert_eq!(imported.jobs.len(), portfolio.jobs.len());
+        for (imported, job) in imported.jobs.iter().zip(&portfolio.jobs) {
+            assert_eq!(imported.company, job.company);
+            assert_eq!(imported.title, job.title);
+            assert_eq!(imported.location, job.location);
+            assert_eq!(imported.start, job.start);
+            assert_eq!(imported.end, job.end);
+            assert_eq!(imported.story, job.story.trim());
+            assert_eq!(list_items(&imported.resp), list_items(&job.resp));
+        }
+        assert_eq!(imported.skills.len(), portfolio.skills.len());
+        for (imported, skill) in imported.skills.iter().zip(&portfolio.skills) {
+            assert_eq!(imported.technology, skill.technology);
+            assert_eq!(imported.categories, skill.categories);
+            assert_eq!(imported.level, skill.level);
+        }
+        assert_eq!(imported.projects.len(), portfolio.projects.len());
+        for (imported, project) in imported.projects.iter().zip(&portfolio.projects) {
+            assert_eq!(imported.title, project.title);
+            assert_eq!(imported.description, project.description);
+            assert_eq!(imported.skills, project.skills);
+            assert_eq!(imported.year, project.year);
+            assert_eq!(imported.features, project.features);
+        }
+        assert_eq!(imported.education.len(), portfolio.education.len());
+        for (imported, education) in imported.education.iter().zip(&portfolio.education) {
+            assert_eq!(imported.institution, education.institution);
+            assert_eq!(imported.title, education.title);
+            assert_eq!(imported.graduation, education.graduation);
+        }
+    }
+
+    #[test]
+    fn a_resume_survives_an_import_and_export() {
+        let json = r#"{
+            "basics": {
+                "email": "someone@example.com",
+                "profiles": [{"network": "GitHub", "url": "https://github.com/someone"}]
+            },
+            "work": [{
+                "name": "Acme Corp",
+                "position": "Engineer",
+                "location": "Bucharest",
+                "startDate": "2019-03-15",
+                "endDate": "2021-11",
+                "summary": "Built things.",
+                "highlights": ["Shipped the thing", "Fixed the other thing"]
+            }],
+            "education": [{"institution": "University", "studyType": "BSc", "endDate": "2015"}],
+            "skills": [{"name": "Rust", "level": "Expert", "keywords": ["Languages"]}],
+            "projects": [{
+                "name": "Tool",
+                "description": "A tool.",
+                "url": "https://example.com/tool",
+                "startDate": "2020",
+                "highlights": ["Fast"],
+                "keywords": ["Rust"]
+            }]
+        }"#;
+        let resume: Resume = serde_json::from_str(json).unwrap();
+        assert!(resume.date_issues().is_empty());
+        let portfolio = Portfolio::from(resume);
+        assert_eq!(
+            portfolio.contact.form,
+            Some(ContactDelivery::Mailto {
+                email: "someone@example.com".to_string()
+            })
+        );
+        let exported = export(&portfolio);
+
+        assert_eq!(exported.basics.profiles.len(), 1);
+        assert_eq!(
+            exported.basics.profiles[0].url,
+            "https://github.com/someone"
+        );
+        let work = &exported.work[0];
+        assert_eq!(work.name, "Acme Corp");
+        assert_eq!(work.position, "Engineer");
+        assert_eq!(work.location.as_deref(), Some("Bucharest"));
+        assert_eq!(work.start_date.as_deref(), Some("2019-03"));
+        assert_eq!(work.end_date.as_deref(), Some("2021-11"));
+        assert_eq!(work.summary, "Built things.");
+        assert_eq!(
+            work.highlights,
+            ["Shipped the thing", "Fixed the other thing"]
+        );
+        assert_eq!(exported.education[0].institution, "University");
+        assert_eq!(exported.education[0].study_type, "BSc");
+        assert_eq!(exported.education[0].end_date.as_deref(), Some("2015"));
+        assert_eq!(exported.skills[0].name, "Rust");
+        assert_eq!(exported.skills[0].level, "Expert");
+        assert_eq!(exported.skills[0].keywords, ["Languages"]);
+        let project = &exported.projects[0];
+        assert_eq!(project.name, "Tool");
+        assert_eq!(project.url.as_deref(), Some("https://example.com/tool"));
+        assert_eq!(project.start_date.as_deref(), Some("2020"));
+        assert_eq!(project.highlights, ["Fast"]);
+        assert_eq!(project.keywords, ["Rust"]);
+    }
+}
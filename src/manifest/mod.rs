@@ -0,0 +1,155 @@
+// Also compiled into build.rs, which is the only user of some items.
+#[allow(dead_code)]
+mod schema;
+pub use schema::*;
+pub mod json_resume;
+
+use std::collections::HashMap;
+use std::rc::Rc;
+use yew::Callback;
+
+#[cfg(invalid_content)]
+compile_error!("the content is invalid, see the errors build.rs reported above");
+
+// The image variants are embedded in both content modes.
+static IMAGES_JSON: &str = include_str!(concat!(env!("OUT_DIR"), "/images.json"));
+
+thread_local! {
+    static IMAGES: HashMap<String, ImageSet> =
+        serde_json::from_str(IMAGES_JSON).expect("images.json is written by build.rs");
+}
+
+/// The variants of a local image; remote images have none.
+pub fn image_set(src: &str) -> Option<ImageSet> {
+    IMAGES.with(|images| images.get(src).cloned())
+}
+
+// Like the images, the repository statistics are a build-time snapshot.
+static REPO_STATS_JSON: &str = include_str!(concat!(env!("OUT_DIR"), "/repo_stats.json"));
+
+thread_local! {
+    static REPO_STATS: RepoSnapshot =
+        serde_json::from_str(REPO_STATS_JSON).expect("repo_stats.json is written by build.rs");
+}
+
+/// The statistics of a project's repository, if the snapshot has them.
+pub fn repo_stats(project_id: &str) -> Option<RepoStats> {
+    REPO_STATS.with(|snapshot| snapshot.repos.get(project_id).cloned())
+}
+
+/// The state of the portfolio content, as seen by the components that render it.
+#[derive(Clone, PartialEq)]
+#[cfg_attr(not(feature = "runtime-content"), allow(dead_code))]
+pub enum Content {
+    Loading,
+    Ready(Rc<Portfolio>),
+    Failed(String),
+}
+
+impl Portfolio {
+    /// Parses and validates a portfolio document using the same rules as build.rs.
+    #[allow(dead_code)]
+    pub fn from_json(json: &str) -> Result<Self, String> {
+        let portfolio: Portfolio = serde_json::from_str(json).map_err(|e| e.to_string())?;
+        match portfolio.validate().as_slice() {
+            [] => Ok(portfolio),
+            issues => Err(issues
+                .iter()
+                .map(|i| i.to_string())
+                .collect::<Vec<_>>()
+                .join("; ")),
+        }
+    }
+}
+
+impl YearMonth {
+    /// The current month according to the browser clock.
+    pub fn today() -> Self {
+        let now = js_sys::Date::new_0();
+        YearMonth {
+            year: now.get_full_year() as u16,
+            month: now.get_month() as u8 + 1,
+        }
+    }
+}
+
+#[cfg(not(feature = "runtime-content"))]
+mod source {
+    use super::*;
+
+    static PORTFOLIO_JSON: &str = include_str!(concat!(env!("OUT_DIR"), "/portfolio.json"));
+
+    thread_local! {
+        static EMBEDDED: Rc<Portfolio> = Rc::new(
+            serde_json::from_str(PORTFOLIO_JSON).expect("portfolio.json is validated by build.rs"),
+        );
+    }
+
+    /// The content is compiled into the binary from `portfolio.toml`, so it is always ready.
+    pub fn load(_loaded: Callback<Content>) -> Content {
+        Content::Ready(EMBEDDED.with(|p| p.clone()))
+    }
+}
+
+#[cfg(feature = "runtime-content")]
+mod source {
+    use super::*;
+    use gloo_net::http::Request;
+    use std::cell::RefCell;
+    use wasm_bindgen_futures::spawn_local;
+
+    const CONTENT_URL: &str = match option_env!("PORTFOLIO_CONTENT_URL") {
+        Some(url) => url,
+        None => "/portfolio.json",
+    };
+
+    thread_local! {
+        static CACHE: RefCell<Option<Rc<Portfolio>>> = const { RefCell::new(None) };
+        static WAITING: RefCell<Vec<Callback<Content>>> = const { RefCell::new(Vec::new()) };
+    }
+
+    /// Returns the cached content, or starts a single shared request and reports
+    /// the result through `loaded`. Failures are not cached, so calling this again retries.
+    pub fn load(loaded: Callback<Content>) -> Content {
+        if let Some(p) = CACHE.with(|c| c.borrow().clone()) {
+            return Content::Ready(p);
+        }
+        let first = WAITING.with(|w| {
+            let mut w = w.borrow_mut();
+            w.push(loaded);
+            w.len() == 1
+        });
+        if first {
+            spawn_local(async {
+                let content = match fetch().await {
+                    Ok(p) => {
+                        let p = Rc::new(p);
+                        CACHE.with(|c| *c.borrow_mut() = Some(p.clone()));
+                        Content::Ready(p)
+                    }
+                    Err(e) => {
+                        log::error!("could not load {}: {}", CONTENT_URL, e);
+                        Content::Failed(e)
+                    }
+                };
+                for loaded in WAITING.with(|w| w.take()) {
+                    loaded.emit(content.clone());
+                }
+            });
+        }
+        Content::Loading
+    }
+
+    async fn fetch() -> Result<Portfolio, String> {
+        let resp = Request::get(CONTENT_URL)
+            .send()
+            .await
+            .map_err(|e| e.to_string())?;
+        if !resp.ok() {
+            return Err(format!("{} {}", resp.status(), resp.status_text()));
+        }
+        Portfolio::from_json(&resp.text().await.map_err(|e| e.to_string())?)
+    }
+}
+
+pub use source::load;
@@ -0,0 +1,1033 @@
+// Shared between the app and build.rs, so this file must only depend on std and serde.
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::{BTreeMap, HashSet};
+
+/// Local images live here and are referenced by their path, e.g. `assets/images/detee.png`;
+/// `examples/build_images.rs` turns them into resized, content-hashed variants.
+pub const IMAGES_DIR: &str = "assets/images/";
+const IMAGE_EXTENSIONS: &[&str] = &["png", "jpg", "jpeg", "webp"];
+
+/// Supported languages; the first one is the default and the fallback for missing translations.
+/// Each language needs a `locales/<code>.toml` bundle.
+pub const LOCALES: &[&str] = &["en", "ro"];
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Portfolio {
+    pub contact: Contact,
+    pub cv: Cv,
+    pub skills: Vec<Skill>,
+    pub jobs: Vec<JobMeta>,
+    pub projects: Vec<ProjectMeta>,
+    pub education: Vec<Education>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Contact {
+    pub schedule: String,
+    pub schedule_note: String,
+    pub email_img: String,
+    pub email_note: String,
+    pub media: Vec<MediaLink>,
+    /// The contact form is only shown when it has somewhere to deliver messages.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub form: Option<ContactDelivery>,
+}
+
+/// Where the contact form sends messages.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "delivery", rename_all = "kebab-case", deny_unknown_fields)]
+pub enum ContactDelivery {
+    /// Opens the visitor's mail client with the message filled in.
+    Mailto { email: String },
+    /// Posts the message as JSON (`name`, `email`, `message`) to the endpoint.
+    Post { endpoint: String },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct MediaLink {
+    pub label: String,
+    pub url: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Cv {
+    pub pdf: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Level(pub usize);
+
+impl std::fmt::Display for Level {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", "✫".repeat(self.0.min(5)))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Skill {
+    /// Tags such as "Linux Networking"; a skill can be in several categories.
+    pub categories: Vec<String>,
+    pub technology: String,
+    pub level: Level,
+    pub note: String,
+    /// Years of hands-on experience.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub years: Option<u8>,
+    /// The year the skill was last used at work or in a project.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_used: Option<u16>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trend: Option<Trend>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub translations: BTreeMap<String, SkillText>,
+}
+
+/// Whether a skill is being used more, as much or less than it used to be.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Trend {
+    Rising,
+    Stable,
+    Fading,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SkillText {
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct JobMeta {
+    pub id: String,
+    pub title: String,
+    pub company: String,
+    pub location: String,
+    pub arrangement: Arrangement,
+    pub employment: Employment,
+    /// Technologies used in this job, by their `technology` name in `skills`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub skills: Vec<String>,
+    pub start: YearMonth,
+    #[serde(with = "present")]
+    pub end: Option<YearMonth>,
+    pub story: String,
+    pub tech: String,
+    pub resp: String,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub translations: BTreeMap<String, JobText>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Arrangement {
+    Remote,
+    Hybrid,
+    OnSite,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Employment {
+    FullTime,
+    Contract,
+    Freelance,
+}
+
+/// Manifest spelling of the variants, which is also the UI string key (`job.on-site`).
+macro_rules! keyed_enum {
+    ($name:ident { $($variant:ident => $key:expr),* }) => {
+        impl $name {
+            pub const ALL: &'static [$name] = &[$($name::$variant),*];
+
+            pub fn key(&self) -> &'static str {
+                match self {
+                    $($name::$variant => $key),*
+                }
+            }
+        }
+    };
+}
+
+keyed_enum!(Arrangement { Remote => "remote", Hybrid => "hybrid", OnSite => "on-site" });
+keyed_enum!(Employment { FullTime => "full-time", Contract => "contract", Freelance => "freelance" });
+keyed_enum!(Trend { Rising => "rising", Stable => "stable", Fading => "fading" });
+keyed_enum!(ProjectStatus { Active => "active", Archived => "archived", Poc => "poc" });
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct JobText {
+    pub title: Option<String>,
+    pub story: Option<String>,
+    pub tech: Option<String>,
+    pub resp: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ProjectMeta {
+    pub id: String,
+    pub title: String,
+    pub description: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub demo: Option<String>,
+    pub source: String,
+    pub img: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub skills: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub year: Option<u16>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<ProjectStatus>,
+    /// An SPDX identifier such as "MIT" or "Apache-2.0".
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub license: Option<String>,
+    /// Markdown for the project page; the card only shows `description`.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub details: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub features: Vec<String>,
+    /// Shown in the gallery of the project page, after `img`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub screenshots: Vec<Screenshot>,
+    /// Links besides `demo` and `source`, such as documentation or a talk.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub links: Vec<MediaLink>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub translations: BTreeMap<String, ProjectText>,
+}
+
+/// The variants of a local image, keyed by its manifest path in `images.json`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ImageSet {
+    pub width: u32,
+    pub height: u32,
+    /// The original file, for browsers that support neither AVIF nor WebP.
+    pub fallback: String,
+    /// `srcset` lists such as "detee-1f2e-320.webp 320w, detee-1f2e-640.webp 640w".
+    pub webp: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub avif: String,
+    /// The average colour, shown while the image loads.
+    pub color: String,
+}
+
+/// A local image as `examples/build_images.rs` left it in `assets/build/images.json`, with
+/// the variants named relative to the images directory.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ProcessedImage {
+    /// The [`content_hash`] of the source file, to tell whether it changed since.
+    pub hash: u32,
+    pub width: u32,
+    pub height: u32,
+    pub fallback: String,
+    /// (width, file) pairs.
+    pub webp: Vec<(u32, String)>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub avif: Vec<(u32, String)>,
+    pub color: String,
+}
+
+impl ProcessedImage {
+    /// The variants as served from `base`, e.g. "/images".
+    pub fn image_set(&self, base: &str) -> ImageSet {
+        let srcset = |variants: &[(u32, String)]| {
+            variants
+                .iter()
+                .map(|(width, file)| format!("{}/{} {}w", base, file, width))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        ImageSet {
+            width: self.width,
+            height: self.height,
+            fallback: format!("{}/{}", base, self.fallback),
+            webp: srcset(&self.webp),
+            avif: srcset(&self.avif),
+            color: self.color.clone(),
+        }
+    }
+}
+
+/// FNV-1a, folded to 32 bits: stable across builds and toolchains, unlike `DefaultHasher`.
+pub fn content_hash(bytes: &[u8]) -> u32 {
+    let hash = bytes.iter().fold(0xcbf2_9ce4_8422_2325_u64, |h, b| {
+        (h ^ *b as u64).wrapping_mul(0x0000_0100_0000_01b3)
+    });
+    (hash ^ (hash >> 32)) as u32
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Screenshot {
+    pub url: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub caption: String,
+}
+
+/// Whether a project is still worked on; a proof of concept was never meant to be.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ProjectStatus {
+    Active,
+    Archived,
+    Poc,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ProjectText {
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub details: Option<String>,
+    pub features: Option<Vec<String>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Education {
+    pub title: String,
+    pub institution: String,
+    pub location: String,
+    pub graduation: u16,
+}
+
+/// A date with month precision, written `08/2022` in the manifest.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+#[serde(try_from = "String", into = "String")]
+pub struct YearMonth {
+    pub year: u16,
+    pub month: u8,
+}
+
+impl YearMonth {
+    /// Number of months from `self` to `end`, counting both ends: 01/2020 - 12/2020 is a year.
+    pub fn months_until(&self, end: YearMonth) -> u32 {
+        let index = |d: YearMonth| d.year as i64 * 12 + d.month as i64;
+        (index(end) - index(*self) + 1).max(0) as u32
+    }
+
+    pub fn next(&self) -> YearMonth {
+        match self.month {
+            12 => YearMonth {
+                year: self.year + 1,
+                month: 1,
+            },
+            m => YearMonth {
+                year: self.year,
+                month: m + 1,
+            },
+        }
+    }
+}
+
+impl std::fmt::Display for YearMonth {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:02}/{}", self.month, self.year)
+    }
+}
+
+impl TryFrom<String> for YearMonth {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let invalid = || format!("invalid date `{}`, expected month/year like 08/2022", value);
+        let (month, year) = value.split_once('/').ok_or_else(invalid)?;
+        let month: u8 = month.parse().map_err(|_| invalid())?;
+        let year: u16 = year.parse().map_err(|_| invalid())?;
+        if !(1..=12).contains(&month) || year.to_string().len() != 4 {
+            return Err(invalid());
+        }
+        Ok(YearMonth { year, month })
+    }
+}
+
+impl From<YearMonth> for String {
+    fn from(date: YearMonth) -> Self {
+        date.to_string()
+    }
+}
+
+/// An end date that may be `"present"`, which is stored as `None`.
+mod present {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(end: &Option<YearMonth>, s: S) -> Result<S::Ok, S::Error> {
+        match end {
+            Some(date) => date.serialize(s),
+            None => s.serialize_str("present"),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<Option<YearMonth>, D::Error> {
+        match String::deserialize(d)? {
+            end if end == "present" => Ok(None),
+            end => YearMonth::try_from(end)
+                .map(Some)
+                .map_err(serde::de::Error::custom),
+        }
+    }
+}
+
+/// Statistics of the projects' repositories, written by
+/// `cargo run --example refresh_repo_stats` so that the build itself stays offline.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RepoSnapshot {
+    /// The day the snapshot was taken, e.g. "2026-10-18".
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fetched: Option<String>,
+    /// Keyed by project id.
+    #[serde(default)]
+    pub repos: BTreeMap<String, RepoStats>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RepoStats {
+    pub stars: u32,
+    /// The day of the last commit, e.g. "2025-03-02".
+    pub last_commit: String,
+    /// Most used first.
+    #[serde(default)]
+    pub languages: Vec<String>,
+    /// An SPDX identifier, as detected by the forge.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub license: Option<String>,
+}
+
+impl RepoStats {
+    pub fn last_commit_month(&self) -> Option<YearMonth> {
+        let mut parts = self.last_commit.splitn(3, '-');
+        let year = parts.next()?.parse().ok()?;
+        let month = parts.next()?.parse().ok()?;
+        let day: u8 = parts.next()?.parse().ok()?;
+        ((1..=12).contains(&month) && (1..=31).contains(&day) && year >= 1970)
+            .then_some(YearMonth { year, month })
+    }
+}
+
+impl RepoSnapshot {
+    /// Errors in the snapshot, and warnings for entries of projects that no longer exist.
+    pub fn validate(&self, portfolio: &Portfolio) -> (Vec<Issue>, Vec<Issue>) {
+        let mut errors = Vec::new();
+        let mut warnings = Vec::new();
+        for (id, stats) in &self.repos {
+            if stats.last_commit_month().is_none() {
+                errors.push(Issue {
+                    path: format!("repos.{}.last_commit", id),
+                    message: "must be a date like 2025-03-02".to_string(),
+                });
+            }
+            if !portfolio.projects.iter().any(|p| &p.id == id) {
+                warnings.push(Issue {
+                    path: format!("repos.{}", id),
+                    message: "no project has this id".to_string(),
+                });
+            }
+        }
+        (errors, warnings)
+    }
+}
+
+/// A semantic error found in an otherwise well-formed manifest.
+/// `path` uses the `jobs[2].start` notation so build.rs can map it back to a manifest line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Issue {
+    pub path: String,
+    pub message: String,
+}
+
+impl std::fmt::Display for Issue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.path, self.message)
+    }
+}
+
+impl Portfolio {
+    pub fn validate(&self) -> Vec<Issue> {
+        let mut issues = Vec::new();
+        let mut issue = |path: String, message: &str| {
+            issues.push(Issue {
+                path,
+                message: message.to_string(),
+            })
+        };
+
+        check_url(&self.cv.pdf, "cv.pdf".to_string(), &mut issue);
+        for (path, image) in self.images() {
+            check_image(image, path, &mut issue);
+        }
+        for (i, m) in self.contact.media.iter().enumerate() {
+            check_text(&m.label, format!("contact.media[{}].label", i), &mut issue);
+            check_url(&m.url, format!("contact.media[{}].url", i), &mut issue);
+        }
+        match &self.contact.form {
+            Some(ContactDelivery::Mailto { email }) if !is_email(email) => {
+                issue("contact.form.email".to_string(), "must be an email address")
+            }
+            Some(ContactDelivery::Post { endpoint }) => {
+                check_url(endpoint, "contact.form.endpoint".to_string(), &mut issue)
+            }
+            _ => {}
+        }
+
+        let mut seen = HashSet::new();
+        for (i, s) in self.skills.iter().enumerate() {
+            if s.categories.is_empty() {
+                issue(
+                    format!("skills[{}].categories", i),
+                    "needs at least one category",
+                );
+            }
+            for (c, category) in s.categories.iter().enumerate() {
+                check_text(
+                    category,
+                    format!("skills[{}].categories[{}]", i, c),
+                    &mut issue,
+                );
+            }
+            check_text(
+                &s.technology,
+                format!("skills[{}].technology", i),
+                &mut issue,
+            );
+            if !(1..=5).contains(&s.level.0) {
+                issue(
+                    format!("skills[{}].level", i),
+                    "level must be between 1 and 5",
+                );
+            }
+            if s.years.is_some_and(|y| !(1..=60).contains(&y)) {
+                issue(
+                    format!("skills[{}].years", i),
+                    "years must be between 1 and 60",
+                );
+            }
+            if s.last_used.is_some_and(|y| !(1900..=2100).contains(&y)) {
+                issue(
+                    format!("skills[{}].last_used", i),
+                    "last_used must be a year like 2021",
+                );
+            }
+            check_locales(&s.translations, format!("skills[{}]", i), &mut issue);
+            let mut categories: Vec<&str> = s.categories.iter().map(String::as_str).collect();
+            categories.sort_unstable();
+            if !seen.insert((categories, s.technology.as_str())) {
+                issue(
+                    format!("skills[{}].technology", i),
+                    "duplicate skill for these categories",
+                );
+            }
+        }
+
+        let mut ids = HashSet::new();
+        for (i, j) in self.jobs.iter().enumerate() {
+            check_id(&j.id, format!("jobs[{}].id", i), &mut ids, &mut issue);
+            check_text(&j.title, format!("jobs[{}].title", i), &mut issue);
+            check_text(&j.company, format!("jobs[{}].company", i), &mut issue);
+            check_text(&j.location, format!("jobs[{}].location", i), &mut issue);
+            if j.end.is_some_and(|end| j.start > end) {
+                issue(
+                    format!("jobs[{}].start", i),
+                    "start date is after the end date",
+                );
+            }
+            check_locales(&j.translations, format!("jobs[{}]", i), &mut issue);
+        }
+
+        let mut ids = HashSet::new();
+        for (i, p) in self.projects.iter().enumerate() {
+            check_id(&p.id, format!("projects[{}].id", i), &mut ids, &mut issue);
+            check_text(&p.title, format!("projects[{}].title", i), &mut issue);
+            if let Some(demo) = &p.demo {
+                check_url(demo, format!("projects[{}].demo", i), &mut issue);
+            }
+            check_url(&p.source, format!("projects[{}].source", i), &mut issue);
+            if p.year.is_some_and(|y| !(1900..=2100).contains(&y)) {
+                issue(
+                    format!("projects[{}].year", i),
+                    "year must be a year like 2021",
+                );
+            }
+            if let Some(license) = &p.license {
+                check_text(license, format!("projects[{}].license", i), &mut issue);
+            }
+            for (f, feature) in p.features.iter().enumerate() {
+                check_text(
+                    feature,
+                    format!("projects[{}].features[{}]", i, f),
+                    &mut issue,
+                );
+            }
+            for (l, link) in p.links.iter().enumerate() {
+                check_text(
+                    &link.label,
+                    format!("projects[{}].links[{}].label", i, l),
+                    &mut issue,
+                );
+                check_url(
+                    &link.url,
+                    format!("projects[{}].links[{}].url", i, l),
+                    &mut issue,
+                );
+            }
+            check_locales(&p.translations, format!("projects[{}]", i), &mut issue);
+        }
+
+        for (i, e) in self.education.iter().enumerate() {
+            check_text(&e.title, format!("education[{}].title", i), &mut issue);
+            check_text(
+                &e.institution,
+                format!("education[{}].institution", i),
+                &mut issue,
+            );
+            check_text(
+                &e.location,
+                format!("education[{}].location", i),
+                &mut issue,
+            );
+            if !(1900..=2100).contains(&e.graduation) {
+                issue(
+                    format!("education[{}].graduation", i),
+                    "graduation must be a year like 2015",
+                );
+            }
+        }
+
+        issues
+    }
+
+    /// Problems that do not stop the build, such as technologies listed by a job or
+    /// project without a matching skill entry.
+    pub fn warnings(&self) -> Vec<Issue> {
+        let known: HashSet<&str> = self.skills.iter().map(|s| s.technology.as_str()).collect();
+        let mut issues = Vec::new();
+        let uses = self
+            .jobs
+            .iter()
+            .enumerate()
+            .map(|(i, j)| (format!("jobs[{}]", i), &j.skills))
+            .chain(
+                self.projects
+                    .iter()
+                    .enumerate()
+                    .map(|(i, p)| (format!("projects[{}]", i), &p.skills)),
+            );
+        for (path, skills) in uses {
+            for (i, technology) in skills.iter().enumerate() {
+                if !known.contains(technology.as_str()) {
+                    issues.push(Issue {
+                        path: format!("{}.skills[{}]", path, i),
+                        message: format!("`{}` has no entry in skills", technology),
+                    });
+                }
+            }
+        }
+        issues
+    }
+
+    /// Every skill category with the number of skills in it, most used first.
+    pub fn categories(&self) -> Vec<(String, usize)> {
+        let mut counts: BTreeMap<&str, usize> = BTreeMap::new();
+        for category in self.skills.iter().flat_map(|s| &s.categories) {
+            *counts.entry(category).or_default() += 1;
+        }
+        let mut categories: Vec<(String, usize)> = counts
+            .into_iter()
+            .map(|(c, n)| (c.to_string(), n))
+            .collect();
+        categories.sort_by_key(|(_, n)| std::cmp::Reverse(*n));
+        categories
+    }
+
+    /// Jobs from the most recent to the oldest: current jobs first, then by end and start date.
+    pub fn jobs_by_date(&self) -> Vec<&JobMeta> {
+        let mut jobs: Vec<&JobMeta> = self.jobs.iter().collect();
+        jobs.sort_by_key(|j| std::cmp::Reverse((j.end.is_none(), j.end, j.start)));
+        jobs
+    }
+
+    /// Months worked up to `today`, counting overlapping jobs once.
+    pub fn experience_months(&self, today: YearMonth) -> u32 {
+        let mut spans: Vec<(YearMonth, YearMonth)> = self
+            .jobs
+            .iter()
+            .map(|j| (j.start, j.end.unwrap_or(today)))
+            .collect();
+        spans.sort();
+        let mut months = 0;
+        let mut covered: Option<YearMonth> = None;
+        for (start, end) in spans {
+            let start = match covered {
+                Some(c) if c >= start => c.next(),
+                _ => start,
+            };
+            if start <= end {
+                months += start.months_until(end);
+                covered = Some(end);
+            }
+        }
+        months
+    }
+
+    /// Every image the site shows, with its path in the manifest.
+    pub fn images(&self) -> Vec<(String, &str)> {
+        let mut images = vec![(
+            "contact.email_img".to_string(),
+            self.contact.email_img.as_str(),
+        )];
+        for (i, p) in self.projects.iter().enumerate() {
+            images.push((format!("projects[{}].img", i), &p.img));
+            for (s, screenshot) in p.screenshots.iter().enumerate() {
+                images.push((
+                    format!("projects[{}].screenshots[{}].url", i, s),
+                    &screenshot.url,
+                ));
+            }
+        }
+        images
+    }
+
+    /// A copy of the portfolio with the texts of `locale` applied over the default language.
+    pub fn localized(&self, locale: &str) -> Portfolio {
+        fn apply(field: &mut String, translation: &Option<String>) {
+            if let Some(t) = translation {
+                field.clone_from(t);
+            }
+        }
+        let mut p = self.clone();
+        for s in &mut p.skills {
+            if let Some(t) = s.translations.get(locale) {
+                apply(&mut s.note, &t.note);
+            }
+        }
+        for j in &mut p.jobs {
+            if let Some(t) = j.translations.get(locale) {
+                apply(&mut j.title, &t.title);
+                apply(&mut j.story, &t.story);
+                apply(&mut j.tech, &t.tech);
+                apply(&mut j.resp, &t.resp);
+            }
+        }
+        for pr in &mut p.projects {
+            if let Some(t) = pr.translations.get(locale) {
+                apply(&mut pr.title, &t.title);
+                apply(&mut pr.description, &t.description);
+                apply(&mut pr.details, &t.details);
+                if let Some(features) = &t.features {
+                    pr.features.clone_from(features);
+                }
+            }
+        }
+        p
+    }
+}
+
+fn check_locales<T>(
+    translations: &BTreeMap<String, T>,
+    path: String,
+    issue: &mut impl FnMut(String, &str),
+) {
+    for locale in translations.keys() {
+        if !LOCALES[1..].contains(&locale.as_str()) {
+            issue(
+                format!("{}.translations.{}", path, locale),
+                "unknown locale; translations are only needed for the non-default entries of LOCALES",
+            );
+        }
+    }
+}
+
+fn check_text(value: &str, path: String, issue: &mut impl FnMut(String, &str)) {
+    if value.trim().is_empty() {
+        issue(path, "must not be empty");
+    }
+}
+
+/// A loose check, meant to catch typos rather than to follow RFC 5322.
+pub fn is_email(value: &str) -> bool {
+    match value.split_once('@') {
+        Some((user, domain)) => {
+            !user.is_empty()
+                && !value.contains(char::is_whitespace)
+                && !domain.contains('@')
+                && domain.split('.').count() > 1
+                && domain.split('.').all(|part| !part.is_empty())
+        }
+        None => false,
+    }
+}
+
+fn check_url(value: &str, path: String, issue: &mut impl FnMut(String, &str)) {
+    if !value.starts_with("https://") && !value.starts_with("http://") {
+        issue(path, "must be an absolute http(s) URL");
+    }
+}
+
+fn check_image(value: &str, path: String, issue: &mut impl FnMut(String, &str)) {
+    let local = value.strip_prefix(IMAGES_DIR).is_some_and(|file| {
+        !file.contains("..")
+            && file.rsplit_once('.').is_some_and(|(_, ext)| {
+                IMAGE_EXTENSIONS.contains(&ext.to_ascii_lowercase().as_str())
+            })
+    });
+    if !local && !value.starts_with("https://") && !value.starts_with("http://") {
+        issue(
+            path,
+            "must be an absolute http(s) URL or a png, jpg or webp file in assets/images/",
+        );
+    }
+}
+
+fn check_id<'a>(
+    value: &'a str,
+    path: String,
+    ids: &mut HashSet<&'a str>,
+    issue: &mut impl FnMut(String, &str),
+) {
+    if value.is_empty()
+        || !value
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+    {
+        issue(
+            path,
+            "id must only contain lowercase letters, digits and dashes",
+        );
+    } else if !ids.insert(value) {
+        issue(path, "duplicate id");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MANIFEST: &str = r#"
+        [contact]
+        schedule = "https://calendly.com/ana"
+        schedule_note = "Book a call"
+        email_img = "assets/images/email.png"
+        email_note = "Or write"
+        media = [{ label = "GitHub", url = "https://github.com/ana" }]
+
+        [cv]
+        pdf = "https://example.com/cv.pdf"
+
+        [[skills]]
+        categories = ["Programming"]
+        technology = "Rust"
+        level = 5
+        note = ""
+
+        [[jobs]]
+        id = "acme"
+        title = "Engineer"
+        company = "Acme"
+        location = "Remote"
+        arrangement = "remote"
+        employment = "full-time"
+        skills = ["Rust"]
+        start = "11/2020"
+        end = "02/2021"
+        story = ""
+        tech = ""
+        resp = ""
+
+        [[projects]]
+        id = "tool"
+        title = "Tool"
+        description = ""
+        source = "https://github.com/ana/tool"
+        img = "https://example.com/tool.png"
+        skills = ["Rust"]
+
+        [[education]]
+        title = "BSc"
+        institution = "University"
+        location = "Cluj"
+        graduation = 2015
+    "#;
+
+    fn portfolio() -> Portfolio {
+        toml::from_str(MANIFEST).unwrap()
+    }
+
+    fn month(date: &str) -> YearMonth {
+        YearMonth::try_from(date.to_string()).unwrap()
+    }
+
+    fn paths(issues: Vec<Issue>) -> Vec<String> {
+        issues.into_iter().map(|i| i.path).collect()
+    }
+
+    #[test]
+    fn valid_manifest_has_no_issues() {
+        let portfolio = portfolio();
+        assert_eq!(portfolio.validate(), []);
+        assert_eq!(portfolio.warnings(), []);
+    }
+
+    #[test]
+    fn level_must_be_between_1_and_5() {
+        let mut portfolio = portfolio();
+        for level in [0, 6] {
+            portfolio.skills[0].level = Level(level);
+            let issues = portfolio.validate();
+            assert_eq!(paths(issues.clone()), ["skills[0].level"]);
+            assert_eq!(issues[0].message, "level must be between 1 and 5");
+        }
+        portfolio.skills[0].level = Level(1);
+        assert_eq!(portfolio.validate(), []);
+    }
+
+    #[test]
+    fn ids_must_be_unique_and_well_formed() {
+        let mut portfolio = portfolio();
+        portfolio.jobs.push(portfolio.jobs[0].clone());
+        portfolio.projects.push(portfolio.projects[0].clone());
+        portfolio.projects[1].id = "Tool 2".to_string();
+        let issues = portfolio.validate();
+        assert_eq!(paths(issues.clone()), ["jobs[1].id", "projects[1].id"]);
+        assert_eq!(issues[0].message, "duplicate id");
+
+        // Jobs and projects have separate ids.
+        let mut portfolio = self::portfolio();
+        portfolio.projects[0].id = "acme".to_string();
+        assert_eq!(portfolio.validate(), []);
+    }
+
+    #[test]
+    fn skills_must_be_unique_per_categories() {
+        let mut portfolio = portfolio();
+        let mut skill = portfolio.skills[0].clone();
+        portfolio.skills.push(skill.clone());
+        skill.categories.push("Systems".to_string());
+        portfolio.skills.push(skill);
+        assert_eq!(paths(portfolio.validate()), ["skills[1].technology"]);
+    }
+
+    #[test]
+    fn unknown_skill_references_are_warnings() {
+        let mut portfolio = portfolio();
+        portfolio.jobs[0].skills.push("Haskell".to_string());
+        portfolio.projects[0].skills.insert(0, "Zig".to_string());
+        assert_eq!(portfolio.validate(), []);
+        let warnings = portfolio.warnings();
+        assert_eq!(
+            paths(warnings.clone()),
+            ["jobs[0].skills[1]", "projects[0].skills[0]"]
+        );
+        assert_eq!(warnings[0].message, "`Haskell` has no entry in skills");
+    }
+
+    #[test]
+    fn job_cannot_end_before_it_starts() {
+        let mut portfolio = portfolio();
+        portfolio.jobs[0].end = Some(month("10/2020"));
+        assert_eq!(paths(portfolio.validate()), ["jobs[0].start"]);
+        portfolio.jobs[0].end = Some(month("11/2020"));
+        assert_eq!(portfolio.validate(), []);
+        portfolio.jobs[0].end = None;
+        assert_eq!(portfolio.validate(), []);
+    }
+
+    #[test]
+    fn other_fields_are_checked() {
+        let mut portfolio = portfolio();
+        portfolio.cv.pdf = "cv.pdf".to_string();
+        portfolio.contact.email_img = "assets/images/../secret.png".to_string();
+        portfolio.skills[0].categories.clear();
+        portfolio.jobs[0].location = " ".to_string();
+        portfolio.jobs[0]
+            .translations
+            .insert("fr".to_string(), JobText::default());
+        portfolio.education[0].graduation = 15;
+        assert_eq!(
+            paths(portfolio.validate()),
+            [
+                "cv.pdf",
+                "contact.email_img",
+                "skills[0].categories",
+                "jobs[0].location",
+                "jobs[0].translations.fr",
+                "education[0].graduation",
+            ]
+        );
+    }
+
+    #[test]
+    fn manifest_dates_are_month_and_year() {
+        assert_eq!(
+            month("08/2022"),
+            YearMonth {
+                year: 2022,
+                month: 8
+            }
+        );
+        assert_eq!(month("8/2022").to_string(), "08/2022");
+        for invalid in [
+            "13/2022", "00/2022", "08/22", "2022/08", "08-2022", "present",
+        ] {
+            assert!(
+                YearMonth::try_from(invalid.to_string()).is_err(),
+                "{}",
+                invalid
+            );
+        }
+        let current: Portfolio = toml::from_str(&MANIFEST.replace("02/2021", "present")).unwrap();
+        assert_eq!(current.jobs[0].end, None);
+        assert!(toml::from_str::<Portfolio>(&MANIFEST.replace("02/2021", "2/21")).is_err());
+    }
+
+    #[test]
+    fn month_arithmetic_crosses_years() {
+        assert_eq!(month("12/2020").next(), month("01/2021"));
+        assert_eq!(month("01/2021").next(), month("02/2021"));
+        assert_eq!(month("01/2020").months_until(month("12/2020")), 12);
+        assert_eq!(month("11/2020").months_until(month("02/2021")), 4);
+        assert_eq!(month("12/2020").months_until(month("01/2021")), 2);
+        assert_eq!(month("03/2021").months_until(month("03/2021")), 1);
+        assert_eq!(month("03/2021").months_until(month("02/2021")), 0);
+        assert!(month("12/2020") < month("01/2021"));
+    }
+
+    #[test]
+    fn experience_counts_overlaps_once() {
+        let mut portfolio = portfolio();
+        let today = month("06/2021");
+        // 11/2020 - 02/2021.
+        assert_eq!(portfolio.experience_months(today), 4);
+
+        // Overlapping 01/2021 - 04/2021 adds 03 and 04.
+        let mut job = portfolio.jobs[0].clone();
+        job.start = month("01/2021");
+        job.end = Some(month("04/2021"));
+        portfolio.jobs.push(job.clone());
+        assert_eq!(portfolio.experience_months(today), 6);
+
+        // A job within another one adds nothing.
+        job.start = month("12/2020");
+        job.end = Some(month("12/2020"));
+        portfolio.jobs.push(job.clone());
+        assert_eq!(portfolio.experience_months(today), 6);
+
+        // An open-ended job counts up to today, from where the others stopped.
+        job.start = month("02/2021");
+        job.end = None;
+        portfolio.jobs.push(job);
+        assert_eq!(portfolio.experience_months(today), 8);
+        assert_eq!(portfolio.experience_months(month("01/2022")), 15);
+    }
+}
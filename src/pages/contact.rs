@@ -1,69 +1,104 @@
+use crate::components::contact_form::{self, ContactForm};
+use crate::components::content;
+use crate::components::picture::Picture;
+use crate::i18n::LocaleProps;
+use crate::manifest::{self, Content};
 use yew::prelude::*;
 
-pub struct Contact;
+pub enum Msg {
+    Content(Content),
+    Retry,
+}
+
+pub struct Contact {
+    content: Content,
+}
+
 impl Component for Contact {
-    type Message = ();
-    type Properties = ();
+    type Message = Msg;
+    type Properties = LocaleProps;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        Self {
+            content: manifest::load(ctx.link().callback(Msg::Content)),
+        }
+    }
 
-    fn create(_ctx: &Context<Self>) -> Self {
-        Self
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        self.content = match msg {
+            Msg::Content(content) => content,
+            Msg::Retry => manifest::load(ctx.link().callback(Msg::Content)),
+        };
+        true
     }
 
-    fn view(&self, _ctx: &Context<Self>) -> Html {
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let locale = ctx.props().locale;
+        let contact = match &self.content {
+            Content::Ready(p) => p.contact.clone(),
+            content => {
+                return content::placeholder(
+                    content,
+                    locale,
+                    4,
+                    ctx.link().callback(|_| Msg::Retry),
+                )
+            }
+        };
         html! {
-            <div class="container fade-in" style="max-width: 600px; height: 90vh">
+            <div class="container fade-in" style="max-width: 600px; min-height: 90vh">
             <div class="columns is-vcentered" style="min-height: 80vh"><div class="column">
 
                 <div class="columns is-mobile flyfromtop">
                     <div class="column is-4"></div>
                     <div class="column">
                         <div class="subtitle is-4 flyfromright">
-                            {"Contact Information"}
+                            {locale.t("contact.title")}
                         </div>
                     </div>
                 </div>
 
 
                 <div class="columns is-mobile flyfromtop">
-                    <div class="column is-4 has-text-right flyfromleft">{"schedule:"}</div>
-                    <div class="column flyfromright">{"11:00 am - 01:00 am UTC"}
+                    <div class="column is-4 has-text-right flyfromleft">{locale.t("contact.schedule")}</div>
+                    <div class="column flyfromright">{&contact.schedule}
                         <div class="subtitle is-7" style="margin-top: 10px">
-                            {"Available every day of the week during these hours."}
+                            {&contact.schedule_note}
                         </div>
                     </div>
                 </div>
                 <div class="columns is-mobile">
-                    <div class="column is-4 has-text-right flyfromleft">{"email:"}</div>
+                    <div class="column is-4 has-text-right flyfromleft">{locale.t("contact.email")}</div>
                     <div class="column flyfromright">
-                        <img class="contactimg"
-                            src="https://filedn.com/lRvVNpEzu7mVLW5g3Ak9iOk/email.png"/>
+                        <Picture class="contactimg" src={contact.email_img.clone()}
+                            alt={locale.t("contact.email")} sizes="300px" eager=true/>
                         <div class="subtitle is-7">
-                            {"Due to the advanced bot protection, copy-paste will not work."}
+                            {&contact.email_note}
                         </div>
                     </div>
                 </div>
 
                 <div class="columns is-mobile flyfrombottom">
-                    <div class="column is-4 has-text-right">{"media:"}</div>
+                    <div class="column is-4 has-text-right">{locale.t("contact.media")}</div>
                     <div class="column" style="max-width: 400px">
-                       <a target="_blank" href="https://blog.gheo.tech">
-                           <button class="button is-small is-black">{"Blog"}</button>
-                       </a>
-                       {" "}
-                       <a target="_blank" href="https://twitter.com/gheotech/">
-                           <button class="button is-small is-black">{"Twitter"}</button>
-                       </a>
-                       {" "}
-                       <a target="_blank" href="https://instagram.com/gheo.tech">
-                           <button class="button is-small is-black">{"Instagram"}</button>
-                       </a>
-                       {" "}
-                       <a target="_blank" href="https://www.linkedin.com/in/ghe0/">
-                           <button class="button is-small is-black">{"LinkedIn"}</button>
-                       </a>
+                       { for contact.media.iter().map(|m| html! { <>
+                           <a target="_blank" href={m.url.clone()}>
+                               <button class="button is-small is-black">{&m.label}</button>
+                           </a>
+                           {" "}
+                       </> }) }
                     </div>
                 </div>
 
+                if let Some(delivery) = contact_form::delivery(contact.form) {
+                    <div class="columns is-mobile flyfrombottom">
+                        <div class="column is-4 has-text-right">{locale.t("contact.write")}</div>
+                        <div class="column">
+                            <ContactForm {delivery} {locale}/>
+                        </div>
+                    </div>
+                }
+
                 <br/><br/><br/>
             </div></div></div>
         }
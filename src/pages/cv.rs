@@ -1,33 +1,18 @@
-use crate::components::job::{Job, JobCard};
+use crate::components::content;
+use crate::components::job::JobCard;
+use crate::components::timeline;
+use crate::i18n::LocaleProps;
+use crate::manifest::{
+    self, json_resume::Resume, Arrangement, Content, Education, Employment, Portfolio, YearMonth,
+};
+use crate::Route;
+use serde::{Deserialize, Serialize};
+use std::rc::Rc;
 use yew::prelude::*;
+use yew_router::history::{History, HistoryListener, Location};
+use yew_router::prelude::*;
 
-const CV_URI: &str = "https://filedn.com/lRvVNpEzu7mVLW5g3Ak9iOk/Gheorghe_Ungureanu_Resume_13-08-2022-00-21-55.pdf";
-
-struct Education<'a> {
-    title: &'a str,
-    institution: &'a str,
-    graduation: &'a str,
-}
-
-static EDUCATION: &[&Education] = &[
-    &Education {
-        title: "Master's Degree in CyberSecurity",
-        institution: "Universtitatea Titu Maiorescu",
-        graduation: "2015",
-    },
-    &Education {
-        title: "Bachlor's Degree in Computer Science",
-        institution: "Universtitatea Titu Maiorescu",
-        graduation: "2013",
-    },
-    &Education {
-        title: "High School, Baccalauréat",
-        institution: "Colegiul National I.L. Caragiale",
-        graduation: "2009",
-    },
-];
-
-impl Education<'_> {
+impl Education {
     fn render(&self) -> Html {
         html! {
             <div class="box" style="background-color: #000000">
@@ -38,7 +23,7 @@ impl Education<'_> {
                     </div>
                     <div class="column has-text-right">
                         <div class="title is-5" style="color: #bfbfbf">{&self.graduation}</div>
-                        <div class="subtitle is-6" style="color: #bfbfbf">{"Bucharest, Romania"}</div>
+                        <div class="subtitle is-6" style="color: #bfbfbf">{&self.location}</div>
                     </div>
                 </div>
             </div>
@@ -46,30 +31,239 @@ impl Education<'_> {
     }
 }
 
-pub struct CV;
+fn json_resume_uri(portfolio: &Portfolio) -> String {
+    let json = serde_json::to_string_pretty(&Resume::from(portfolio)).unwrap();
+    let mut uri = "data:application/json;charset=utf-8,".to_string();
+    for b in json.bytes() {
+        match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                uri.push(b as char)
+            }
+            _ => uri.push_str(&format!("%{:02X}", b)),
+        }
+    }
+    uri
+}
+
+#[derive(Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum View {
+    #[default]
+    List,
+    Timeline,
+}
+
+impl View {
+    fn is_list(&self) -> bool {
+        *self == View::List
+    }
+}
+
+/// The query string of the CV page, e.g. `/cv?view=timeline`, or `/cv?job=detee` to open a job.
+#[derive(Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CvQuery {
+    #[serde(default, skip_serializing_if = "View::is_list")]
+    view: View,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    job: Option<String>,
+}
+
+impl CvQuery {
+    /// The CV scrolled to one job, opened on its story.
+    pub fn job(id: &str) -> Self {
+        CvQuery {
+            job: Some(id.to_string()),
+            ..Default::default()
+        }
+    }
+}
+
+pub enum Msg {
+    Content(Content),
+    Retry,
+    Arrangement(Option<Arrangement>),
+    Employment(Option<Employment>),
+    SetView(View),
+    UrlChanged,
+    Focus(String),
+}
+
+pub struct CV {
+    content: Content,
+    arrangement: Option<Arrangement>,
+    employment: Option<Employment>,
+    view: View,
+    focus: (String, u32),
+    _listener: Option<HistoryListener>,
+}
+
+impl CV {
+    fn url_query(ctx: &Context<Self>) -> CvQuery {
+        ctx.link()
+            .location()
+            .and_then(|l| l.query::<CvQuery>().ok())
+            .unwrap_or_default()
+    }
+}
+
 impl Component for CV {
-    type Message = ();
-    type Properties = ();
+    type Message = Msg;
+    type Properties = LocaleProps;
 
-    fn create(_ctx: &Context<Self>) -> Self {
-        Self
+    fn create(ctx: &Context<Self>) -> Self {
+        let query = CV::url_query(ctx);
+        Self {
+            content: manifest::load(ctx.link().callback(Msg::Content)),
+            arrangement: None,
+            employment: None,
+            view: query.view,
+            focus: match query.job {
+                Some(id) => (id, 1),
+                None => (String::new(), 0),
+            },
+            _listener: ctx.link().history().map(|h| {
+                let link = ctx.link().clone();
+                h.listen(move || link.send_message(Msg::UrlChanged))
+            }),
+        }
     }
 
-    fn view(&self, _ctx: &Context<Self>) -> Html {
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::Content(content) => self.content = content,
+            Msg::Retry => self.content = manifest::load(ctx.link().callback(Msg::Content)),
+            Msg::Arrangement(a) => self.arrangement = a,
+            Msg::Employment(e) => self.employment = e,
+            Msg::SetView(view) => {
+                if let (Some(history), Some(route)) =
+                    (ctx.link().history(), ctx.link().route::<Route>())
+                {
+                    match view {
+                        View::List => history.push(route),
+                        view => {
+                            let query = CvQuery {
+                                view,
+                                ..Default::default()
+                            };
+                            if let Err(e) = history.push_with_query(route, query) {
+                                log::error!("could not update the URL: {}", e);
+                            }
+                        }
+                    }
+                }
+                self.view = view;
+            }
+            Msg::UrlChanged => {
+                let query = CV::url_query(ctx);
+                let focus = query.job.filter(|id| *id != self.focus.0);
+                if query.view == self.view && focus.is_none() {
+                    return false;
+                }
+                self.view = query.view;
+                if let Some(id) = focus {
+                    self.focus = (id, self.focus.1 + 1);
+                }
+            }
+            Msg::Focus(id) => self.focus = (id, self.focus.1 + 1),
+        }
+        true
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let locale = ctx.props().locale;
+        let portfolio = match &self.content {
+            Content::Ready(p) => p.localized(locale.code()),
+            content => {
+                return content::placeholder(
+                    content,
+                    locale,
+                    8,
+                    ctx.link().callback(|_| Msg::Retry),
+                )
+            }
+        };
+        // Only values that some job actually has get a filter button.
+        macro_rules! filter {
+            ($field:ident, $kind:ident) => {{
+                let button = |value: Option<$kind>, label: String| {
+                    let class = match self.$field == value {
+                        true => "is-white",
+                        false => "is-black",
+                    };
+                    html! {
+                        <button class={classes!("button", "is-small", "is-rounded", class)}
+                            onclick={ctx.link().callback(move |_| Msg::$kind(value))}>
+                            {label}
+                        </button>
+                    }
+                };
+                html! {
+                    <div class="buttons is-centered cvfilter">
+                        {button(None, locale.t("cv.all"))}
+                        { for $kind::ALL.iter()
+                            .filter(|v| portfolio.jobs.iter().any(|j| j.$field == **v))
+                            .map(|v| button(Some(*v), locale.t(&format!("job.{}", v.key())))) }
+                    </div>
+                }
+            }};
+        }
+        let jobs: Vec<_> = portfolio
+            .jobs_by_date()
+            .into_iter()
+            .filter(|j| self.arrangement.is_none_or(|a| j.arrangement == a))
+            .filter(|j| self.employment.is_none_or(|e| j.employment == e))
+            .collect();
+        let view_button = |view: View, label: String| {
+            let class = match self.view == view {
+                true => "is-white",
+                false => "is-black",
+            };
+            html! {
+                <button class={classes!("button", "is-small", class)}
+                    onclick={ctx.link().callback(move |_| Msg::SetView(view))}>
+                    {label}
+                </button>
+            }
+        };
         html! {
             <div class="container fade-in" style="max-width: 1200px">
                 <div class="title is-4 container has-text-centered">
-                    {"Curriculum Vitae"}
+                    {locale.t("cv.title")}
                     <div class="jobtext" style="font-size: 14px">
-                        <a href={CV_URI}>{"download pdf"}</a></div>
+                        <a href={portfolio.cv.pdf.clone()}>{locale.t("cv.pdf")}</a>
+                        {" · "}
+                        <a href={json_resume_uri(&portfolio)} download="resume.json">
+                            {locale.t("cv.json_resume")}</a>
+                    </div>
+                    <div class="subtitle is-6 gray4" style="margin-top: 8px">
+                        {locale.t("cv.experience")} {" "}
+                        {locale.duration(portfolio.experience_months(YearMonth::today()))}
+                    </div>
+                </div>
+                <div class="buttons has-addons is-centered cvfilter">
+                    {view_button(View::List, locale.t("cv.list"))}
+                    {view_button(View::Timeline, locale.t("cv.timeline"))}
                 </div>
-                < JobCard job={Job::GheoTech} />
-                < JobCard job={Job::Kape} />
-                < JobCard job={Job::Vodafone} />
-                < JobCard job={Job::IBM} />
-                < JobCard job={Job::Freelance} />
+                {filter!(arrangement, Arrangement)}
+                {filter!(employment, Employment)}
+                if jobs.is_empty() {
+                    <div class="subtitle is-6 has-text-centered gray4">{locale.t("cv.no_jobs")}</div>
+                }
+                if self.view == View::Timeline {
+                    {timeline::render(&jobs, &portfolio.education, locale,
+                        ctx.link().callback(Msg::Focus))}
+                }
+                { for jobs.iter().map(|j| {
+                    let focus = match self.focus.0 == j.id {
+                        true => self.focus.1,
+                        false => 0,
+                    };
+                    html! {
+                        < JobCard key={j.id.clone()} job={Rc::new((*j).clone())} {locale} {focus} />
+                    }
+                }) }
                 <br/>
-                <div>{EDUCATION.iter().map(|e| e.render()).collect::<Html>()}</div>
+                <div>{portfolio.education.iter().map(|e| e.render()).collect::<Html>()}</div>
             </div>
         }
     }
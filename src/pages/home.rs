@@ -1,18 +1,20 @@
 use yew::prelude::*;
 use crate::{Route, Link};
+use crate::i18n::LocaleProps;
 
 static DEMO_CODE: &'static str = include_str!("../components/data/home_demo.rs");
 
 pub struct Home;
 impl Component for Home {
     type Message = ();
-    type Properties = ();
+    type Properties = LocaleProps;
 
     fn create(_ctx: &Context<Self>) -> Self {
         Self
     }
 
-    fn view(&self, _ctx: &Context<Self>) -> Html {
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let locale = ctx.props().locale;
         html! {
         <div class="container fade-in" style="margin-top: -27px">
             <div class="columns is-vcentered is-gapless">
@@ -20,47 +22,43 @@ impl Component for Home {
                 <div class="container" style="max-width: 450px">
                   <br/>
                   <div class="title is-4">
-                      {"Gheorghe Technologies SRL"}
+                      {locale.t("home.title")}
                   </div>
                   <div class="subtitle is-5 is-italic">
-                      {"Software Development, DevOps, Web2.0+Web3.0"}
+                      {locale.t("home.tagline")}
                   </div>
-                  <p>{"Welcome to Gheorghe's portfolio website!"}</p>
+                  <p>{locale.t("home.welcome")}</p>
                   <br/>
-                  <p>{"This website should offer you all the information needed in case
-                      you are seeking development or consultancy services, including:
-                      Programming (Rust, GoLang), Linux, Cloud, DevOps,
-                      Infrastructure Architecture,
-                      Blockchain, dApps."}</p>
+                  <p>{locale.t("home.intro")}</p>
                   <div class="container" style="max-width: 250px">
                       <br/>
-                      <Link<Route> to={Route::Skills}>
+                      <Link<Route> to={Route::Skills.localized(locale)}>
                           <button class="button is-white is-outlined is-fullwidth">
-                             {"My Skills"}
+                             {locale.t("home.skills")}
                           </button>
                       </Link<Route>>
                       <br/>
-                      <Link<Route> to={Route::CV}>
+                      <Link<Route> to={Route::CV.localized(locale)}>
                           <button class="button is-white is-outlined is-fullwidth">
-                             {"Curriculum Vitae"}
+                             {locale.t("home.cv")}
                           </button>
                       </Link<Route>>
                       <br/>
-                      <Link<Route> to={Route::Projects}>
+                      <Link<Route> to={Route::Projects.localized(locale)}>
                           <button class="button is-white is-outlined is-fullwidth">
-                              {"Open-Source Projects"}
+                              {locale.t("home.projects")}
                           </button>
                       </Link<Route>>
                       <br/>
-                      <Link<Route> to={Route::Contact}>
+                      <Link<Route> to={Route::Contact.localized(locale)}>
                           <button class="button is-white is-outlined is-fullwidth">
-                              {"Contact Information"}
+                              {locale.t("home.contact")}
                           </button>
                       </Link<Route>>
                       <br/>
                       <a href="https://blog.gheo.tech">
                           <button class="button is-black is-fullwidth">
-                              {"Blog"}
+                              {locale.t("home.blog")}
                           </button>
                       </a>
                       <br/>
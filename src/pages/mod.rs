@@ -1,5 +1,6 @@
 pub mod contact;
 pub mod cv;
 pub mod home;
+pub mod project;
 pub mod projects;
 pub mod skills;
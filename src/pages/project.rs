@@ -0,0 +1,146 @@
+use crate::components::content;
+use crate::components::gallery::Gallery;
+use crate::components::markdown;
+use crate::components::projects;
+use crate::i18n::Locale;
+use crate::manifest::{self, Content, ProjectMeta, Screenshot};
+use crate::Route;
+use std::rc::Rc;
+use yew::prelude::*;
+use yew_router::prelude::*;
+
+#[derive(PartialEq, Properties)]
+pub struct Props {
+    pub locale: Locale,
+    pub id: String,
+}
+
+pub enum Msg {
+    Content(Content),
+    Retry,
+}
+
+/// The full page of one project, at `/projects/<id>`.
+pub struct ProjectDetail {
+    content: Content,
+}
+
+impl Component for ProjectDetail {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        Self {
+            content: manifest::load(ctx.link().callback(Msg::Content)),
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        self.content = match msg {
+            Msg::Content(content) => content,
+            Msg::Retry => manifest::load(ctx.link().callback(Msg::Content)),
+        };
+        true
+    }
+
+    fn changed(&mut self, _ctx: &Context<Self>) -> bool {
+        // Following the previous/next links would otherwise land at the bottom of the page.
+        if let Some(window) = web_sys::window() {
+            window.scroll_to_with_x_and_y(0.0, 0.0);
+        }
+        true
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let locale = ctx.props().locale;
+        let portfolio = match &self.content {
+            Content::Ready(p) => p.localized(locale.code()),
+            content => {
+                return content::placeholder(
+                    content,
+                    locale,
+                    8,
+                    ctx.link().callback(|_| Msg::Retry),
+                )
+            }
+        };
+        let all_projects = html! {
+            <Link<Route> classes={classes!("gray4")} to={Route::Projects.localized(locale)}>
+                {"❮ "}{locale.t("projects.back")}
+            </Link<Route>>
+        };
+        let index = portfolio
+            .projects
+            .iter()
+            .position(|p| p.id == ctx.props().id);
+        let index = match index {
+            Some(i) => i,
+            None => {
+                return html! {
+                    <div class="container fade-in has-text-centered" style="max-width: 1000px">
+                        <div class="subtitle is-5">{locale.t("projects.not_found")}</div>
+                        {all_projects}
+                    </div>
+                }
+            }
+        };
+        let project = &portfolio.projects[index];
+        let facts = projects::facts(project, locale);
+        let pictures: Vec<Screenshot> = std::iter::once(Screenshot {
+            url: project.img.clone(),
+            caption: String::new(),
+        })
+        .chain(project.screenshots.iter().cloned())
+        .collect();
+        let neighbour = |project: Option<&ProjectMeta>, label: &str, class: &str| match project {
+            Some(p) => html! {
+                <div class={classes!("column", class.to_string())}>
+                    <div class="gray4">{locale.t(label)}</div>
+                    <Link<Route> to={Route::ProjectDetail { id: p.id.clone() }.localized(locale)}>
+                        {&p.title}
+                    </Link<Route>>
+                </div>
+            },
+            None => html! { <div class="column"/> },
+        };
+
+        html! {
+            <div class="container fade-in projectpage" style="max-width: 1000px">
+                {all_projects}
+                <div class="title is-3 has-text-centered">{&project.title}</div>
+                <p class="subtitle is-italic is-6 has-text-centered">{&project.description}</p>
+                if !facts.is_empty() {
+                    <p class="gray4 projectfacts has-text-centered">{facts}</p>
+                }
+                {projects::tech_chips(project, locale)}
+                {projects::repo_badges(project, locale)}
+                <Gallery pictures={Rc::new(pictures)} title={project.title.clone()} {locale}/>
+                if !project.details.is_empty() {
+                    <div class="jobtext">{markdown::render(&project.details)}</div>
+                }
+                if !project.features.is_empty() {
+                    <div class="title is-5">{locale.t("projects.features")}</div>
+                    <ul class="projectfeatures">
+                        { for project.features.iter().map(|f| html! { <li>{f}</li> }) }
+                    </ul>
+                }
+                <div class="buttons is-centered projectlinks">
+                    {projects::demo_button(project, locale)}
+                    {projects::source_button(project, locale)}
+                    { for project.links.iter().map(|l| html! {
+                        <a class="button is-white is-small is-outlined" target="_blank"
+                            href={l.url.clone()}>
+                            {&l.label}
+                        </a>
+                    }) }
+                </div>
+                <div class="columns is-mobile projectnav">
+                    {neighbour(index.checked_sub(1).map(|i| &portfolio.projects[i]),
+                        "projects.previous", "has-text-left")}
+                    {neighbour(portfolio.projects.get(index + 1),
+                        "projects.next", "has-text-right")}
+                </div>
+            </div>
+        }
+    }
+}
@@ -1,24 +1,296 @@
+use crate::components::content;
+use crate::components::projects::{self, ProjectCard};
+use crate::i18n::LocaleProps;
+use crate::manifest::{self, Content, ProjectMeta, ProjectStatus};
+use crate::Route;
+use serde::{Deserialize, Serialize};
+use std::rc::Rc;
 use yew::prelude::*;
-use crate::components::projects::{Project, ProjectCard};
+use yew_router::history::{History, HistoryListener, Location};
+use yew_router::prelude::*;
+
+#[derive(Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ProjectSort {
+    /// The manifest order.
+    #[default]
+    Featured,
+    Newest,
+    Oldest,
+    Title,
+}
+
+impl ProjectSort {
+    const ALL: [ProjectSort; 4] = [
+        ProjectSort::Featured,
+        ProjectSort::Newest,
+        ProjectSort::Oldest,
+        ProjectSort::Title,
+    ];
+
+    fn key(self) -> &'static str {
+        match self {
+            ProjectSort::Featured => "projects.featured",
+            ProjectSort::Newest => "projects.newest",
+            ProjectSort::Oldest => "projects.oldest",
+            ProjectSort::Title => "projects.by_title",
+        }
+    }
+
+    fn is_featured(&self) -> bool {
+        *self == ProjectSort::Featured
+    }
+
+    /// Projects without a year go last either way.
+    fn apply(self, projects: &mut [&ProjectMeta]) {
+        match self {
+            ProjectSort::Featured => {}
+            ProjectSort::Newest => projects.sort_by_key(|p| std::cmp::Reverse(p.year)),
+            ProjectSort::Oldest => projects.sort_by_key(|p| (p.year.is_none(), p.year)),
+            ProjectSort::Title => projects.sort_by_key(|p| p.title.to_lowercase()),
+        }
+    }
+}
+
+/// The query string of the projects page, e.g. `/projects?tech=Rust&status=active&year=2021`.
+#[derive(Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ProjectsQuery {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tech: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<ProjectStatus>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub year: Option<u16>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub license: Option<String>,
+    #[serde(default, skip_serializing_if = "ProjectSort::is_featured")]
+    pub sort: ProjectSort,
+}
+
+impl ProjectsQuery {
+    fn matches(&self, project: &ProjectMeta) -> bool {
+        self.tech
+            .as_ref()
+            .is_none_or(|t| project.skills.contains(t))
+            && self.status.is_none_or(|s| project.status == Some(s))
+            && self.year.is_none_or(|y| project.year == Some(y))
+            && self
+                .license
+                .as_ref()
+                .is_none_or(|l| projects::license(project).as_ref() == Some(l))
+    }
+}
+
+pub enum Msg {
+    Content(Content),
+    Retry,
+    Filter(ProjectsQuery),
+    UrlChanged,
+}
+
+pub struct Projects {
+    content: Content,
+    query: ProjectsQuery,
+    _listener: Option<HistoryListener>,
+}
+
+impl Projects {
+    fn url_query(ctx: &Context<Self>) -> ProjectsQuery {
+        ctx.link()
+            .location()
+            .and_then(|l| l.query::<ProjectsQuery>().ok())
+            .unwrap_or_default()
+    }
+}
 
-pub struct Projects;
 impl Component for Projects {
-    type Message = ();
-    type Properties = ();
+    type Message = Msg;
+    type Properties = LocaleProps;
 
-    fn create(_ctx: &Context<Self>) -> Self {
-        Self
+    fn create(ctx: &Context<Self>) -> Self {
+        Self {
+            content: manifest::load(ctx.link().callback(Msg::Content)),
+            query: Projects::url_query(ctx),
+            _listener: ctx.link().history().map(|h| {
+                let link = ctx.link().clone();
+                h.listen(move || link.send_message(Msg::UrlChanged))
+            }),
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::Content(content) => self.content = content,
+            Msg::Retry => self.content = manifest::load(ctx.link().callback(Msg::Content)),
+            Msg::Filter(query) => {
+                if let (Some(history), Some(route)) =
+                    (ctx.link().history(), ctx.link().route::<Route>())
+                {
+                    if query == ProjectsQuery::default() {
+                        history.push(route);
+                    } else if let Err(e) = history.push_with_query(route, query.clone()) {
+                        log::error!("could not update the URL: {}", e);
+                    }
+                }
+                self.query = query;
+            }
+            Msg::UrlChanged => {
+                let query = Projects::url_query(ctx);
+                if query == self.query {
+                    return false;
+                }
+                self.query = query;
+            }
+        }
+        true
     }
 
-    fn view(&self, _ctx: &Context<Self>) -> Html {
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let locale = ctx.props().locale;
+        let portfolio = match &self.content {
+            Content::Ready(p) => p.localized(locale.code()),
+            content => {
+                return content::placeholder(
+                    content,
+                    locale,
+                    8,
+                    ctx.link().callback(|_| Msg::Retry),
+                )
+            }
+        };
+        let mut projects: Vec<&ProjectMeta> = portfolio
+            .projects
+            .iter()
+            .filter(|p| self.query.matches(p))
+            .collect();
+        self.query.sort.apply(&mut projects);
+        let mut techs: Vec<&String> = Vec::new();
+        for technology in portfolio.projects.iter().flat_map(|p| &p.skills) {
+            if !techs.contains(&technology) {
+                techs.push(technology);
+            }
+        }
+        let mut years: Vec<u16> = portfolio.projects.iter().filter_map(|p| p.year).collect();
+        years.sort_unstable_by(|a, b| b.cmp(a));
+        years.dedup();
+        let mut licenses: Vec<String> = Vec::new();
+        for license in portfolio.projects.iter().filter_map(projects::license) {
+            if !licenses.contains(&license) {
+                licenses.push(license);
+            }
+        }
+
+        let button = |selected: bool, label: String, query: ProjectsQuery| {
+            let class = match selected {
+                true => "is-white",
+                false => "is-black",
+            };
+            html! {
+                <button class={classes!("button", "is-small", "is-rounded", class)}
+                    onclick={ctx.link().callback(move |_| Msg::Filter(query.clone()))}>
+                    {label}
+                </button>
+            }
+        };
+        let status_button = |status: Option<ProjectStatus>| {
+            let label = match status {
+                Some(s) => locale.t(&format!("projects.{}", s.key())),
+                None => locale.t("projects.all"),
+            };
+            button(
+                self.query.status == status,
+                label,
+                ProjectsQuery {
+                    status,
+                    ..self.query.clone()
+                },
+            )
+        };
+        // Clicking the selected technology again shows every project.
+        let tech_button = |technology: &String| {
+            let selected = self.query.tech.as_ref() == Some(technology);
+            button(
+                selected,
+                technology.clone(),
+                ProjectsQuery {
+                    tech: (!selected).then(|| technology.clone()),
+                    ..self.query.clone()
+                },
+            )
+        };
+        let year_button = |year: u16| {
+            let selected = self.query.year == Some(year);
+            button(
+                selected,
+                year.to_string(),
+                ProjectsQuery {
+                    year: (!selected).then_some(year),
+                    ..self.query.clone()
+                },
+            )
+        };
+        let license_button = |license: String| {
+            let selected = self.query.license.as_ref() == Some(&license);
+            button(
+                selected,
+                license.clone(),
+                ProjectsQuery {
+                    license: (!selected).then_some(license),
+                    ..self.query.clone()
+                },
+            )
+        };
+        let sort_button = |sort: ProjectSort| {
+            button(
+                self.query.sort == sort,
+                locale.t(sort.key()),
+                ProjectsQuery {
+                    sort,
+                    ..self.query.clone()
+                },
+            )
+        };
+
         html! {
             <div class="container fade-in" style="max-width: 1000px">
-               <div class="subtitle is-4 has-text-centered">{"Projects"}</div>
-               < ProjectCard project={Project::DeTEE} />
-               < ProjectCard project={Project::GheoTech} />
-               < ProjectCard project={Project::MerkleTree} />
-               < ProjectCard project={Project::RustPhantom} />
-               < ProjectCard project={Project::ActixServer} />
+               <div class="subtitle is-4 has-text-centered">{locale.t("projects.title")}</div>
+               <div class="buttons is-centered cvfilter">
+                   {status_button(None)}
+                   { for ProjectStatus::ALL.iter()
+                       .filter(|s| portfolio.projects.iter().any(|p| p.status == Some(**s)))
+                       .map(|s| status_button(Some(*s))) }
+               </div>
+               <div class="buttons is-centered cvfilter">
+                   { for techs.into_iter().map(tech_button) }
+               </div>
+               if !years.is_empty() || !licenses.is_empty() {
+                   <div class="buttons is-centered cvfilter">
+                       if !years.is_empty() {
+                           <span class="gray4">{locale.t("projects.year")}</span>
+                           { for years.into_iter().map(year_button) }
+                       }
+                       if !licenses.is_empty() {
+                           <span class="gray4">{locale.t("projects.license_filter")}</span>
+                           { for licenses.into_iter().map(license_button) }
+                       }
+                   </div>
+               }
+               <div class="buttons is-centered cvfilter">
+                   <span class="gray4">{locale.t("projects.sort")}</span>
+                   { for ProjectSort::ALL.into_iter().map(sort_button) }
+               </div>
+               if projects.is_empty() {
+                   <div class="subtitle is-6 has-text-centered gray4">
+                       {locale.t("projects.no_projects")}
+                       <br/>
+                       <a onclick={ctx.link().callback(|_| Msg::Filter(ProjectsQuery::default()))}>
+                           {locale.t("projects.show_all")}
+                       </a>
+                   </div>
+               }
+               { for projects.into_iter().map(|p|
+                   html! { < ProjectCard key={p.id.clone()} project={Rc::new(p.clone())} {locale} /> }
+               ) }
             </div>
         }
     }
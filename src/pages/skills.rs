@@ -1,23 +1,25 @@
 use yew::prelude::*;
 use crate::components::skills::SkillList;
+use crate::i18n::LocaleProps;
 
 pub struct Skills;
 impl Component for Skills {
     type Message = ();
-    type Properties = ();
+    type Properties = LocaleProps;
 
     fn create(_ctx: &Context<Self>) -> Self {
         Self
     }
 
 
-    fn view(&self, _ctx: &Context<Self>) -> Html {
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let locale = ctx.props().locale;
         html! {
             <div class="container fade-in">
                 <div class="subtitle is-4 has-text-centered">
-                    {"Skills"}
+                    {locale.t("skills.title")}
                 </div>
-                <SkillList/>
+                <SkillList {locale}/>
             </div>
         }
     }